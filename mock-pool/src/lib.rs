@@ -0,0 +1,239 @@
+// Pool de staking simulado para las pruebas del contrato de Tandas. Sigue la interfaz del pool
+// de NEAR con el mismo modelo de acciones, las recompensas se agregan a mano con
+// agregar_recompensa en lugar de llegar con cada época.
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::UnorderedMap;
+use near_sdk::json_types::U128;
+use near_sdk::{env, near_bindgen, setup_alloc, AccountId, Promise};
+
+setup_alloc!();
+
+// Épocas que el pool retiene lo desdelegado antes de permitir retirarlo.
+pub const EPOCAS_DESDELEGACION: u64 = 4;
+
+// Calcula monto * parte / total sin desbordar u128 con montos en yoctoNEAR.
+fn prorrata(monto: u128, parte: u128, total: u128) -> u128 {
+    if total == 0 {
+        return 0;
+    }
+
+    let mut cociente = monto / total * parte;
+    let resto = monto % total;
+    let (mut q, mut r) = (0u128, 0u128);
+    for bit in (0..128).rev() {
+        q <<= 1;
+        r <<= 1;
+        if r >= total {
+            q += 1;
+            r -= total;
+        }
+        if parte >> bit & 1 == 1 {
+            r += resto;
+            if r >= total {
+                q += 1;
+                r -= total;
+            }
+        }
+    }
+    cociente += q;
+    cociente
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct Cuenta {
+    pub acciones: u128,
+    pub no_stakeado: u128,
+    pub epoca_disponible: u64,
+}
+
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct MockPool {
+    total_acciones: u128,
+    total_stakeado: u128,
+    cuentas: UnorderedMap<AccountId, Cuenta>,
+}
+
+impl Default for MockPool {
+    fn default() -> Self {
+        Self {
+            total_acciones: 0,
+            total_stakeado: 0,
+            cuentas: UnorderedMap::new(b"cuentas".to_vec()),
+        }
+    }
+}
+
+#[near_bindgen]
+impl MockPool {
+    #[payable]
+    pub fn deposit_and_stake(&mut self) {
+        let monto = env::attached_deposit();
+        assert!(monto > 0, "El depósito debe ser mayor a 0.");
+
+        let acciones = if self.total_acciones == 0 {
+            monto
+        } else {
+            prorrata(monto, self.total_acciones, self.total_stakeado)
+        };
+
+        let id_cuenta = env::predecessor_account_id();
+        let mut cuenta = self.cuentas.get(&id_cuenta).unwrap_or_default();
+        cuenta.acciones += acciones;
+        self.cuentas.insert(&id_cuenta, &cuenta);
+
+        self.total_acciones += acciones;
+        self.total_stakeado += monto;
+    }
+
+    pub fn unstake(&mut self, amount: U128) {
+        let id_cuenta = env::predecessor_account_id();
+        let mut cuenta = self.cuentas.get(&id_cuenta).unwrap_or_default();
+
+        // * Se redondea hacia arriba para que el pool nunca entregue más de lo que vale
+        let mut acciones = prorrata(amount.0, self.total_acciones, self.total_stakeado);
+        if prorrata(acciones, self.total_stakeado, self.total_acciones) < amount.0 {
+            acciones += 1;
+        }
+        assert!(
+            amount.0 > 0 && acciones <= cuenta.acciones,
+            "La cuenta no tiene suficiente saldo en staking."
+        );
+
+        cuenta.acciones -= acciones;
+        cuenta.no_stakeado += amount.0;
+        cuenta.epoca_disponible = env::epoch_height() + EPOCAS_DESDELEGACION;
+        self.cuentas.insert(&id_cuenta, &cuenta);
+
+        self.total_acciones -= acciones;
+        self.total_stakeado -= amount.0;
+    }
+
+    pub fn withdraw(&mut self, amount: U128) -> Promise {
+        let id_cuenta = env::predecessor_account_id();
+        let mut cuenta = self.cuentas.get(&id_cuenta).unwrap_or_default();
+
+        assert!(
+            amount.0 > 0 && amount.0 <= cuenta.no_stakeado,
+            "La cuenta no tiene suficiente saldo fuera de staking."
+        );
+        assert!(
+            env::epoch_height() >= cuenta.epoca_disponible,
+            "Los fondos podrán retirarse a partir de la época {}.",
+            cuenta.epoca_disponible
+        );
+
+        cuenta.no_stakeado -= amount.0;
+        self.cuentas.insert(&id_cuenta, &cuenta);
+
+        Promise::new(id_cuenta).transfer(amount.0)
+    }
+
+    pub fn get_account_staked_balance(&self, account_id: AccountId) -> U128 {
+        let acciones = self.cuentas.get(&account_id).unwrap_or_default().acciones;
+        U128(prorrata(acciones, self.total_stakeado, self.total_acciones))
+    }
+
+    pub fn get_account_unstaked_balance(&self, account_id: AccountId) -> U128 {
+        U128(
+            self.cuentas
+                .get(&account_id)
+                .unwrap_or_default()
+                .no_stakeado,
+        )
+    }
+
+    // Simula la recompensa de una época, se reparte entre las cuentas según sus acciones.
+    pub fn agregar_recompensa(&mut self, monto: U128) {
+        assert!(self.total_acciones > 0, "No hay fondos en staking.");
+        self.total_stakeado += monto.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::json_types::ValidAccountId;
+    use near_sdk::test_utils::VMContextBuilder;
+    use near_sdk::{testing_env, MockedBlockchain, VMContext};
+    use std::convert::TryFrom;
+
+    const NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+
+    fn contexto(cuenta: &str, deposito: u128, epoca: u64) -> VMContext {
+        VMContextBuilder::new()
+            .predecessor_account_id(ValidAccountId::try_from(cuenta).unwrap())
+            .current_account_id(ValidAccountId::try_from("pool.near").unwrap())
+            .attached_deposit(deposito)
+            .epoch_height(epoca)
+            .build()
+    }
+
+    #[test]
+    fn recompensa_se_reparte_por_acciones() {
+        testing_env!(contexto("tanda.near", 3 * NEAR, 1));
+        let mut pool = MockPool::default();
+        pool.deposit_and_stake();
+        testing_env!(contexto("otro.near", NEAR, 1));
+        pool.deposit_and_stake();
+
+        pool.agregar_recompensa(U128(NEAR));
+        assert_eq!(
+            pool.get_account_staked_balance("tanda.near".into()).0,
+            3 * NEAR + 3 * NEAR / 4
+        );
+        assert_eq!(
+            pool.get_account_staked_balance("otro.near".into()).0,
+            NEAR + NEAR / 4
+        );
+
+        // * Quien deposita después no participa de la recompensa anterior
+        testing_env!(contexto("nuevo.near", 5 * NEAR, 2));
+        pool.deposit_and_stake();
+        assert_eq!(
+            pool.get_account_staked_balance("nuevo.near".into()).0,
+            5 * NEAR
+        );
+    }
+
+    #[test]
+    fn retiro_despues_de_la_espera() {
+        testing_env!(contexto("tanda.near", 2 * NEAR, 1));
+        let mut pool = MockPool::default();
+        pool.deposit_and_stake();
+        pool.agregar_recompensa(U128(NEAR / 2));
+
+        testing_env!(contexto("tanda.near", 0, 1));
+        pool.unstake(U128(2 * NEAR + NEAR / 2));
+        assert_eq!(pool.get_account_staked_balance("tanda.near".into()).0, 0);
+        assert_eq!(
+            pool.get_account_unstaked_balance("tanda.near".into()).0,
+            2 * NEAR + NEAR / 2
+        );
+
+        testing_env!(contexto("tanda.near", 0, 1 + EPOCAS_DESDELEGACION));
+        pool.withdraw(U128(2 * NEAR + NEAR / 2));
+        assert_eq!(pool.get_account_unstaked_balance("tanda.near".into()).0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Los fondos podrán retirarse a partir de la época 5.")]
+    fn retiro_antes_de_la_espera() {
+        testing_env!(contexto("tanda.near", NEAR, 1));
+        let mut pool = MockPool::default();
+        pool.deposit_and_stake();
+        pool.unstake(U128(NEAR));
+
+        testing_env!(contexto("tanda.near", 0, 4));
+        pool.withdraw(U128(NEAR));
+    }
+
+    #[test]
+    #[should_panic(expected = "La cuenta no tiene suficiente saldo en staking.")]
+    fn desdelegar_mas_de_lo_stakeado() {
+        testing_env!(contexto("tanda.near", NEAR, 1));
+        let mut pool = MockPool::default();
+        pool.deposit_and_stake();
+        pool.unstake(U128(NEAR + 1));
+    }
+}
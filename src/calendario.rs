@@ -0,0 +1,84 @@
+use crate::date_handling;
+
+// * CALENDARIO
+// ? Documento iCalendar (RFC 5545). Los UID son estables para que al volver a importar
+// ? el calendario se actualicen los eventos en lugar de duplicarse.
+
+const DOMINIO_UID: &str = "tanda-dapp";
+const LONGITUD_LINEA: usize = 75;
+
+pub struct Evento {
+    pub uid: String,
+    pub fecha: String,
+    pub resumen: String,
+    pub descripcion: String,
+}
+
+impl Evento {
+    pub fn new(clave: &str, tipo: &str, indice: usize, fecha: &str) -> Self {
+        Self {
+            uid: format!("{}-{}-{}@{}", clave, tipo, indice + 1, DOMINIO_UID),
+            fecha: String::from(fecha),
+            resumen: String::new(),
+            descripcion: String::new(),
+        }
+    }
+}
+
+pub fn generar_calendario(nombre: &str, eventos: &[Evento]) -> String {
+    let sello = date_handling::formato_ical(&date_handling::calcular_inicio());
+    let mut lineas = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:-//{}//Tandas//ES", DOMINIO_UID),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("METHOD:PUBLISH"),
+        format!("X-WR-CALNAME:{}", escapar(nombre)),
+    ];
+
+    for evento in eventos.iter() {
+        lineas.push(String::from("BEGIN:VEVENT"));
+        lineas.push(format!("UID:{}", evento.uid));
+        lineas.push(format!("DTSTAMP:{}", sello));
+        lineas.push(format!(
+            "DTSTART:{}",
+            date_handling::formato_ical(&evento.fecha)
+        ));
+        lineas.push(format!("SUMMARY:{}", escapar(&evento.resumen)));
+        lineas.push(format!("DESCRIPTION:{}", escapar(&evento.descripcion)));
+        lineas.push(String::from("END:VEVENT"));
+    }
+    lineas.push(String::from("END:VCALENDAR"));
+
+    lineas
+        .iter()
+        .map(|linea| plegar(linea))
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+fn escapar(texto: &str) -> String {
+    texto
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Las líneas de más de 75 octetos se continúan en la siguiente con un espacio al inicio.
+fn plegar(linea: &str) -> String {
+    let mut resultado = String::new();
+    let mut longitud = 0;
+
+    for caracter in linea.chars() {
+        if longitud + caracter.len_utf8() > LONGITUD_LINEA {
+            resultado.push_str("\r\n ");
+            longitud = 1;
+        }
+        resultado.push(caracter);
+        longitud += caracter.len_utf8();
+    }
+    resultado.push_str("\r\n");
+
+    resultado
+}
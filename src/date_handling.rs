@@ -6,21 +6,24 @@ const FORMATO_FECHA: &str = "%Y-%m-%d %H:%M:%S UTC";
 
 // TODO: Change date time to only dates
 
-pub fn calcular_inicio() -> String {
-    let timestamp = env::block_timestamp().to_string()[..10]
+fn timestamp_actual() -> i64 {
+    env::block_timestamp().to_string()[..10]
         .parse::<i64>()
-        .unwrap();
+        .unwrap()
+}
+
+pub fn calcular_inicio() -> String {
+    let timestamp = timestamp_actual();
 
-    let parse_inicio = NaiveDateTime::from_timestamp(timestamp, 0);
-    let inicio_utc: DateTime<Utc> = DateTime::from_utc(parse_inicio, Utc);
+    let inicio_utc: DateTime<Utc> = DateTime::from_timestamp(timestamp, 0).unwrap();
 
     inicio_utc.to_string()
 }
 
 pub fn agregar_dias(fecha: &str, dias: i64) -> String {
     //let formato_fecha = "%Y-%m-%d %H:%M:%S";
-    let parse_fecha = NaiveDateTime::parse_from_str(&fecha, FORMATO_FECHA).unwrap();
-    let fecha_utc: DateTime<Utc> = DateTime::from_utc(parse_fecha, Utc);
+    let parse_fecha = NaiveDateTime::parse_from_str(fecha, FORMATO_FECHA).unwrap();
+    let fecha_utc: DateTime<Utc> = parse_fecha.and_utc();
 
     fecha_utc
         .checked_add_signed(Duration::days(dias))
@@ -28,6 +31,36 @@ pub fn agregar_dias(fecha: &str, dias: i64) -> String {
         .to_string()
 }
 
+pub fn dias_entre(inicio: &str, fin: &str) -> i64 {
+    let parse_inicio = NaiveDateTime::parse_from_str(inicio, FORMATO_FECHA).unwrap();
+    let parse_fin = NaiveDateTime::parse_from_str(fin, FORMATO_FECHA).unwrap();
+
+    (parse_fin - parse_inicio).num_days()
+}
+
+pub fn fecha_alcanzada(fecha: &str) -> bool {
+    let parse_fecha = NaiveDateTime::parse_from_str(fecha, FORMATO_FECHA).unwrap();
+
+    parse_fecha.and_utc().timestamp() <= timestamp_actual()
+}
+
+// La fecha final de un periodo es su último día, vence al terminar ese día.
+pub fn periodo_vencido(fin: &str) -> bool {
+    fecha_alcanzada(&agregar_dias(fin, 1))
+}
+
+// El periodo vence dentro de los próximos días indicados o ya venció.
+pub fn periodo_por_vencer(fin: &str, dias: i64) -> bool {
+    fecha_alcanzada(&agregar_dias(fin, 1 - dias))
+}
+
+// Fecha en formato UTC de iCalendar (RFC 5545), por ejemplo 20240131T180000Z.
+pub fn formato_ical(fecha: &str) -> String {
+    let parse_fecha = NaiveDateTime::parse_from_str(fecha, FORMATO_FECHA).unwrap();
+
+    parse_fecha.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
 // pub fn prueba_fecha(&self, dias: i64) {
 //     let a = &env::block_timestamp().to_string()[..10];
 //     let n = a.parse::<i64>().unwrap();
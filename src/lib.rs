@@ -1,23 +1,97 @@
-use crate::types::{Pago, Periodo, Tanda, Usuario};
+use crate::types::{
+    AccionAdministrativa, Adelanto, Asiento, AsignacionTurnos, Atestacion, CajaAhorro,
+    CarteraPrestamos, ConceptoMovimiento, ConfiguracionAhorro, Desdelegacion, Deuda, Disputa,
+    EstadoCuentaTanda, EstadoMonedero, EstadoPool, EstadoStaking, Exposicion, FondoReserva,
+    Liquidacion, MedidaArbitral, MovimientoSaldo, OpcionesTanda, OrdenTurnos, Pago, Perfil,
+    Periodo, Prestamo, Propuesta, Resolucion, ResumenAhorro, RolOrganizador,
+    SolicitudAdministrativa, Tanda, TipoPropuesta, Traspaso, TurnoIntegrante, Usuario,
+    MAX_DISPUTAS_INTEGRANTE, PARTICIPACION_COMPLETA,
+};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::UnorderedMap;
-use near_sdk::{env, near_bindgen, setup_alloc, AccountId, Promise};
+use near_sdk::collections::{UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::{
+    env, ext_contract, near_bindgen, setup_alloc, AccountId, Gas, Promise, PromiseOrValue,
+    PromiseResult,
+};
 use std::cmp;
 use std::collections::{HashMap, HashSet};
 use std::vec::Vec;
 
+mod calendario;
 mod date_handling;
 mod log_generator;
+mod sorteo;
 mod types;
 
 const MAX_PAGE_SIZE: u64 = 10;
+const GAS_CALLBACK: Gas = 20_000_000_000_000;
+const GAS_STAKING: Gas = 50_000_000_000_000;
+const GAS_CONSULTA: Gas = 10_000_000_000_000;
+// Épocas que el pool retiene lo desdelegado antes de permitir retirarlo.
+const EPOCAS_DESDELEGACION: u64 = 4;
+// Días antes del fin de un periodo en que sus fondos dejan el pool para llegar a tiempo al pago.
+const DIAS_DESDELEGACION: i64 = 3;
+// Días del periodo de referencia al que se llevan las cuotas para medir la exposición.
+const DIAS_EXPOSICION: u128 = 30;
 
 fn one_near() -> u128 {
-    u128::from_str_radix("1000000000000000000000000", 10).unwrap()
+    "1000000000000000000000000".parse::<u128>().unwrap()
+}
+
+// Calcula monto * parte / total sin desbordar u128 con montos en yoctoNEAR.
+fn prorrata(monto: u128, parte: u128, total: u128) -> u128 {
+    if total == 0 {
+        return 0;
+    }
+
+    let mut cociente = monto / total * parte;
+    let resto = monto % total;
+    let (mut q, mut r) = (0u128, 0u128);
+    for bit in (0..128).rev() {
+        q <<= 1;
+        r <<= 1;
+        if r >= total {
+            q += 1;
+            r -= total;
+        }
+        if parte >> bit & 1 == 1 {
+            r += resto;
+            if r >= total {
+                q += 1;
+                r -= total;
+            }
+        }
+    }
+    cociente += q;
+    cociente
 }
 
 setup_alloc!();
 
+#[ext_contract(ext_self)]
+pub trait ExtTandaDapp {
+    fn resolver_retiro(&mut self, cuenta: AccountId, monto: U128) -> bool;
+    fn resolver_consulta_delegacion(&mut self, clave: String, monto: U128) -> bool;
+    fn resolver_delegacion(&mut self, clave: String, monto: U128, acciones: U128) -> bool;
+    fn resolver_consulta_desdelegacion(&mut self, clave: String, monto: Option<U128>) -> bool;
+    fn resolver_desdelegacion(&mut self, clave: String, desdelegacion: Desdelegacion) -> bool;
+    fn resolver_retiro_staking(
+        &mut self,
+        clave: String,
+        principal: U128,
+        rendimiento: U128,
+    ) -> bool;
+}
+
+#[ext_contract(ext_pool)]
+pub trait PoolStaking {
+    fn deposit_and_stake(&mut self);
+    fn unstake(&mut self, amount: U128);
+    fn withdraw(&mut self, amount: U128);
+    fn get_account_staked_balance(&self, account_id: AccountId) -> U128;
+}
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct TandaDapp {
@@ -25,6 +99,24 @@ pub struct TandaDapp {
     periodos_tanda: UnorderedMap<String, Vec<Periodo>>,
     usuarios: UnorderedMap<AccountId, Usuario>,
     pagos: UnorderedMap<String, HashMap<String, Vec<Pago>>>,
+    saldos: UnorderedMap<AccountId, u128>,
+    monederos: UnorderedMap<String, HashMap<AccountId, u128>>,
+    movimientos_saldo: Vector<MovimientoSaldo>,
+    movimientos_cuenta: UnorderedMap<AccountId, Vec<u64>>,
+    propuestas: UnorderedMap<String, Vec<Propuesta>>,
+    liquidaciones: UnorderedMap<String, Vec<Liquidacion>>,
+    deudas: UnorderedMap<String, Vec<Deuda>>,
+    traspasos: UnorderedMap<String, Vec<Traspaso>>,
+    solicitudes: UnorderedMap<String, Vec<SolicitudAdministrativa>>,
+    disputas: UnorderedMap<String, Vec<Disputa>>,
+    staking: UnorderedMap<String, EstadoStaking>,
+    pools_staking: UnorderedMap<AccountId, EstadoPool>,
+    prestamos: UnorderedMap<String, CarteraPrestamos>,
+    reservas: UnorderedMap<String, FondoReserva>,
+    incumplimientos: UnorderedMap<AccountId, Vec<String>>,
+    verificadores: UnorderedSet<AccountId>,
+    atestaciones: UnorderedMap<AccountId, Vec<Atestacion>>,
+    limite_exposicion: u32,
 }
 
 impl Default for TandaDapp {
@@ -34,6 +126,24 @@ impl Default for TandaDapp {
             periodos_tanda: UnorderedMap::new(b"p".to_vec()),
             usuarios: UnorderedMap::new(b"u".to_vec()),
             pagos: UnorderedMap::new(b"h".to_vec()),
+            saldos: UnorderedMap::new(b"s".to_vec()),
+            monederos: UnorderedMap::new(b"w".to_vec()),
+            movimientos_saldo: Vector::new(b"m".to_vec()),
+            movimientos_cuenta: UnorderedMap::new(b"i".to_vec()),
+            propuestas: UnorderedMap::new(b"g".to_vec()),
+            liquidaciones: UnorderedMap::new(b"l".to_vec()),
+            deudas: UnorderedMap::new(b"d".to_vec()),
+            traspasos: UnorderedMap::new(b"x".to_vec()),
+            solicitudes: UnorderedMap::new(b"o".to_vec()),
+            disputas: UnorderedMap::new(b"r".to_vec()),
+            staking: UnorderedMap::new(b"k".to_vec()),
+            pools_staking: UnorderedMap::new(b"f".to_vec()),
+            prestamos: UnorderedMap::new(b"n".to_vec()),
+            reservas: UnorderedMap::new(b"v".to_vec()),
+            incumplimientos: UnorderedMap::new(b"y".to_vec()),
+            verificadores: UnorderedSet::new(b"a".to_vec()),
+            atestaciones: UnorderedMap::new(b"c".to_vec()),
+            limite_exposicion: 0,
         }
     }
 }
@@ -47,6 +157,57 @@ impl TandaDapp {
         num_integrantes: u32,
         monto: u32,
         periodo: u32,
+        opciones: Option<OpcionesTanda>,
+    ) {
+        self.crear(
+            nombre_tanda,
+            num_integrantes,
+            monto,
+            periodo,
+            opciones,
+            None,
+        );
+    }
+
+    // Todos aportan cada periodo y retiran lo ahorrado al vencimiento, no hay turnos.
+    #[payable]
+    pub fn crear_caja_ahorro(
+        &mut self,
+        nombre_caja: String,
+        num_integrantes: u32,
+        monto: u32,
+        periodo: u32,
+        ahorro: ConfiguracionAhorro,
+        opciones: Option<OpcionesTanda>,
+    ) {
+        assert!(
+            ahorro.num_periodos > 0,
+            "La caja de ahorro necesita al menos 1 periodo."
+        );
+        assert!(
+            ahorro.penalizacion_retiro <= 100,
+            "La penalización por retiro anticipado debe estar entre 0 y 100."
+        );
+
+        self.crear(
+            nombre_caja,
+            num_integrantes,
+            monto,
+            periodo,
+            opciones,
+            Some(CajaAhorro::new(ahorro)),
+        );
+    }
+
+    // ! MÉTODO INTERNO
+    fn crear(
+        &mut self,
+        nombre_tanda: String,
+        num_integrantes: u32,
+        monto: u32,
+        periodo: u32,
+        opciones: Option<OpcionesTanda>,
+        ahorro: Option<CajaAhorro>,
     ) {
         // * Validación de errores
         assert!(
@@ -55,7 +216,7 @@ impl TandaDapp {
         );
 
         assert!(
-            nombre_tanda != "",
+            !nombre_tanda.is_empty(),
             "El nombre de la Tanda no puede estar vacío."
         );
         assert!(
@@ -65,10 +226,26 @@ impl TandaDapp {
 
         assert!(monto > 0, "El monto a ahorrar tiene que ser mayor a 0.");
         assert!(periodo > 0, "El periodo no puede ser menor a 1.");
+        if let Some(pool) = opciones
+            .as_ref()
+            .and_then(|opciones| opciones.pool_staking.as_ref())
+        {
+            if !pool.is_empty() {
+                self.validar_pool_habilitado(pool);
+            }
+        }
 
         // * Creación de Tanda
 
-        let tanda = Tanda::new(String::from(&nombre_tanda), num_integrantes, monto, periodo);
+        let mut tanda = Tanda::new(
+            String::from(&nombre_tanda),
+            num_integrantes,
+            monto,
+            periodo,
+            opciones.unwrap_or_default(),
+        );
+        tanda.ahorro = ahorro;
+        tanda.actualizar_fecha_final();
         self.tandas.insert(&tanda.id, &tanda);
 
         // * Registro de usuario y tanda, generación de periodos de tanda.
@@ -77,20 +254,32 @@ impl TandaDapp {
         self.generar_periodos(String::from(&tanda.id));
 
         // * Registro de log
-        let msg = format!(
-            "{} creó la tanda {}, con id: {}, {} personas ahorrarán {} NEAR cada {} días.",
-            env::predecessor_account_id(),
-            &nombre_tanda,
-            &tanda.id,
-            num_integrantes,
-            monto,
-            periodo
-        );
+        let msg = match &tanda.ahorro {
+            Some(caja) => format!(
+                "{} creó la caja de ahorro {}, con id: {}, {} personas ahorrarán {} NEAR cada {} días durante {} periodos.",
+                env::predecessor_account_id(),
+                &nombre_tanda,
+                &tanda.id,
+                num_integrantes,
+                monto,
+                periodo,
+                caja.num_periodos
+            ),
+            None => format!(
+                "{} creó la tanda {}, con id: {}, {} personas ahorrarán {} NEAR cada {} días.",
+                env::predecessor_account_id(),
+                &nombre_tanda,
+                &tanda.id,
+                num_integrantes,
+                monto,
+                periodo
+            ),
+        };
         env::log(msg.as_bytes());
     }
 
     pub fn consultar_tanda(&self, clave: String) -> Option<Tanda> {
-        assert!(clave != "", "El campo de clave no debe estar vacío.");
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío.");
         self.tandas.get(&clave)
     }
 
@@ -141,15 +330,153 @@ impl TandaDapp {
         }
     }
 
+    pub fn consultar_estado_cuenta(
+        &self,
+        id_cuenta: Option<String>,
+        desde: Option<u64>,
+        limite: Option<u64>,
+    ) -> Vec<EstadoCuentaTanda> {
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+        let limite = cmp::min(limite.unwrap_or(MAX_PAGE_SIZE), MAX_PAGE_SIZE) as usize;
+
+        self.consultar_tandas_inscritas(Some(String::from(&cuenta)))
+            .iter()
+            .skip(desde.unwrap_or(0) as usize)
+            .take(limite)
+            .filter_map(|clave| self.estado_cuenta_tanda(clave, &cuenta))
+            .collect()
+    }
+
     // ! MÉTODO INTERNO
-    pub fn buscar_tandas(&self, lista_tandas: Vec<String>) -> Vec<Tanda> {
-        let mut result = Vec::<Tanda>::new();
+    fn estado_cuenta_tanda(&self, clave: &String, cuenta: &AccountId) -> Option<EstadoCuentaTanda> {
+        let tanda = self.tandas.get(clave)?;
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+
+        let proximo = periodos
+            .iter()
+            .position(|periodo| !periodo.integrantes_pagados.contains(cuenta));
+        let vencidos: Vec<&Periodo> = periodos
+            .iter()
+            .filter(|periodo| {
+                !periodo.integrantes_pagados.contains(cuenta)
+                    && date_handling::periodo_vencido(&periodo.fin)
+            })
+            .collect();
+
+        // * Turnos de los asientos que ocupa la cuenta
+        let asientos: Vec<u32> = tanda
+            .asientos
+            .iter()
+            .filter(|asiento| asiento.participaciones.contains_key(cuenta))
+            .map(|asiento| asiento.numero)
+            .collect();
+        let turnos = periodos
+            .iter()
+            .enumerate()
+            .filter(|(_, periodo)| match periodo.asiento_en_turno {
+                Some(numero) => asientos.contains(&numero),
+                None => &periodo.usuario_en_turno == cuenta,
+            })
+            .map(|(n, periodo)| TurnoIntegrante {
+                indice_periodo: n as u32,
+                num_asiento: periodo.asiento_en_turno,
+                fecha: String::from(&periodo.fin),
+                pagado: periodo.tanda_pagada,
+            })
+            .collect();
+
+        let total_pagado: u128 = self
+            .pagos
+            .get(clave)
+            .and_then(|historial| historial.get(cuenta).cloned())
+            .unwrap_or_default()
+            .iter()
+            .map(|pago| pago.monto.0)
+            .sum();
+        let total_recibido: u128 = periodos
+            .iter()
+            .filter_map(|periodo| periodo.beneficiarios.get(cuenta))
+            .map(|monto| monto.0)
+            .sum();
+
+        Some(EstadoCuentaTanda {
+            tanda: String::from(clave),
+            nombre_tanda: String::from(&tanda.nombre_tanda),
+            estado: String::from(&tanda.estado),
+            proximo_periodo: proximo.map(|n| n as u32),
+            fecha_limite: proximo.map(|n| String::from(&periodos[n].fin)),
+            monto_a_pagar: U128(proximo.map_or(0, |n| tanda.adeudo_total(cuenta, &periodos[n]))),
+            periodos_vencidos: vencidos.len() as u32,
+            adeudo_vencido: U128(
+                vencidos
+                    .iter()
+                    .map(|periodo| tanda.adeudo_total(cuenta, periodo))
+                    .sum(),
+            ),
+            turnos,
+            total_pagado: U128(total_pagado),
+            total_recibido: U128(total_recibido),
+        })
+    }
 
-        for n in 0..lista_tandas.len() {
-            result.push(self.tandas.get(&lista_tandas[n]).unwrap())
+    pub fn exportar_calendario(&self, clave: String, id_cuenta: Option<String>) -> String {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+
+        // * Un evento por fecha límite de aportación
+        let mut eventos: Vec<calendario::Evento> = periodos
+            .iter()
+            .enumerate()
+            .map(|(n, periodo)| {
+                let mut evento = calendario::Evento::new(&clave, "aportacion", n, &periodo.fin);
+                evento.resumen = format!(
+                    "{}: fecha límite de aportación, periodo #{}",
+                    &tanda.nombre_tanda,
+                    n + 1
+                );
+                evento.descripcion = match &id_cuenta {
+                    Some(cuenta) => format!(
+                        "Adeudo de {}: {} yoctoNEAR.",
+                        cuenta,
+                        tanda.adeudo_total(cuenta, periodo)
+                    ),
+                    None => format!("Cuota del periodo: {} NEAR por asiento.", periodo.monto),
+                };
+                evento
+            })
+            .collect();
+
+        // * Y uno por cada turno de cobro de la cuenta
+        if let Some(cuenta) = &id_cuenta {
+            if let Some(estado) = self.estado_cuenta_tanda(&clave, cuenta) {
+                for turno in estado.turnos.iter() {
+                    let n = turno.indice_periodo as usize;
+                    let mut evento = calendario::Evento::new(&clave, "cobro", n, &turno.fecha);
+                    evento.resumen = format!(
+                        "{}: {} recibe la Tanda, periodo #{}",
+                        &tanda.nombre_tanda,
+                        cuenta,
+                        n + 1
+                    );
+                    evento.descripcion = format!(
+                        "Turno del asiento {}.",
+                        turno.num_asiento.unwrap_or_default()
+                    );
+                    eventos.push(evento);
+                }
+            }
         }
 
-        result
+        calendario::generar_calendario(&tanda.nombre_tanda, &eventos)
+    }
+
+    // ! MÉTODO INTERNO
+    pub fn buscar_tandas(&self, lista_tandas: Vec<String>) -> Vec<Tanda> {
+        lista_tandas
+            .iter()
+            .map(|id| self.tandas.get(id).unwrap())
+            .collect()
     }
 
     pub fn consultar_tandas(&self) -> Vec<Option<Tanda>> {
@@ -171,7 +498,7 @@ impl TandaDapp {
     }
 
     pub fn agregar_integrante(&mut self, clave: String) {
-        assert!(clave != "", "El campo de clave no debe estar vacío.");
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío.");
         let id_cuenta = env::predecessor_account_id();
 
         let valido = self.validar_integrante(String::from(&clave), String::from(&id_cuenta));
@@ -185,331 +512,516 @@ impl TandaDapp {
         let tanda = self.tandas.get(&clave);
         assert!(tanda.is_some(), "La tanda no existe.");
         let mut tanda_unwrap = tanda.unwrap();
+        self.validar_ingreso(&tanda_unwrap, &id_cuenta);
         tanda_unwrap.agregar_integrante(String::from(&id_cuenta));
+        self.validar_exposicion(&tanda_unwrap, &id_cuenta);
 
         self.tandas.insert(&clave, &tanda_unwrap);
 
         self.registrar_usuario(id_cuenta.to_string(), String::from(&tanda_unwrap.id), false);
     }
 
-    pub fn consultar_integrantes(&self, clave: String) -> HashSet<AccountId> {
-        assert!(clave != "", "El campo de clave no debe estar vacío.");
+    pub fn tomar_asientos(&mut self, clave: String, num_asientos: u32) {
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío.");
+        assert!(num_asientos > 0, "Se debe tomar al menos un asiento.");
 
         let tanda = self.tandas.get(&clave);
         assert!(tanda.is_some(), "La tanda no existe.");
+        let mut tanda_unwrap = tanda.unwrap();
 
-        tanda.unwrap().integrantes
-    }
-
-    // ! MÉTODO INTERNO
-    fn validar_integrante(&self, id_tanda: String, id_cuenta: AccountId) -> bool {
-        match self.tandas.get(&id_tanda) {
-            Some(tanda) => tanda.integrantes.contains(&id_cuenta),
-            None => false,
-        }
-    }
-
-    #[payable]
-    pub fn agregar_integrante_pago(&mut self, clave: String) -> bool {
-        // * Validaciones
-        let tanda_check = self.tandas.get(&clave);
-        assert!(tanda_check.is_some(), "La tanda no existe.");
-        let tanda = tanda_check.unwrap();
-        let monto = env::attached_deposit();
-
+        let disponibles = tanda_unwrap.num_integrantes - tanda_unwrap.asientos.len() as u32;
         assert!(
-            monto == one_near().checked_mul(tanda.monto as u128).unwrap(),
-            "Sólo se pueden realizar pagos por la cantidad establecida en la Tanda ({} NEAR).",
-            tanda.monto
+            num_asientos <= disponibles,
+            "La Tanda sólo tiene {} asientos disponibles.",
+            disponibles
         );
 
         let id_cuenta = env::predecessor_account_id();
-        let valido = self.validar_integrante(String::from(&tanda.id), String::from(&id_cuenta));
-
-        assert!(
-            valido,
-            "El usuario {} no es integrante de esta tanda.",
-            &id_cuenta
-        );
-
-        assert!(
-            self.periodos_tanda.get(&clave).is_some(),
-            "Los periodos para esta tanda no están inicializados."
-        );
+        let nuevo_integrante = !tanda_unwrap.integrantes.contains(&id_cuenta);
+        if nuevo_integrante {
+            self.validar_ingreso(&tanda_unwrap, &id_cuenta);
+        }
 
-        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
-        let indice = self.validar_periodo(String::from(&clave), Some(String::from(&id_cuenta)));
+        for _n in 0..num_asientos {
+            tanda_unwrap.agregar_integrante(String::from(&id_cuenta));
+        }
+        self.validar_exposicion(&tanda_unwrap, &id_cuenta);
 
-        assert!(
-            indice >= 0,
-            "El usuario {} no puede realizar pagos. 
-            Ya se realizaron todos los pagos correspondientes a esta Tanda",
-            &id_cuenta
-        );
+        self.tandas.insert(&clave, &tanda_unwrap);
 
-        // * Registro en periodos
-        periodos[indice as usize]
-            .integrantes_pagados
-            .insert(String::from(&id_cuenta));
+        if nuevo_integrante {
+            self.registrar_usuario(id_cuenta, String::from(&tanda_unwrap.id), false);
+        }
+    }
 
-        let monto_a_sumar = monto.checked_div(one_near()).unwrap().to_string();
-        periodos[indice as usize].cantidad_recaudada += monto_a_sumar.parse::<u32>().unwrap();
+    pub fn tomar_participacion(
+        &mut self,
+        clave: String,
+        num_asiento: Option<u32>,
+        participacion: u32,
+    ) {
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío.");
 
-        self.periodos_tanda.insert(&clave, &periodos);
+        let tanda = self.tandas.get(&clave);
+        assert!(tanda.is_some(), "La tanda no existe.");
+        let mut tanda_unwrap = tanda.unwrap();
 
-        self.validar_pago_tanda(String::from(&clave), indice);
+        let id_cuenta = env::predecessor_account_id();
+        let nuevo_integrante = !tanda_unwrap.integrantes.contains(&id_cuenta);
+        if nuevo_integrante {
+            self.validar_ingreso(&tanda_unwrap, &id_cuenta);
+        }
 
-        // * Registro en historial de pagos
-        let new_payment = Pago::new(tanda.monto, date_handling::calcular_inicio());
+        tanda_unwrap.agregar_participacion(String::from(&id_cuenta), num_asiento, participacion);
+        self.validar_exposicion(&tanda_unwrap, &id_cuenta);
 
-        match self.pagos.get(&clave) {
-            Some(mut historial) => {
-                if historial.get_mut(&id_cuenta).is_some() {
-                    let hist_user = historial.get_mut(&id_cuenta).unwrap();
-                    let mut hist = self.pagos.get(&clave).unwrap();
+        self.tandas.insert(&clave, &tanda_unwrap);
 
-                    hist_user.push(new_payment);
-                    hist.insert(String::from(&id_cuenta), hist_user.to_vec());
+        if nuevo_integrante && tanda_unwrap.integrantes.contains(&id_cuenta) {
+            self.registrar_usuario(id_cuenta, String::from(&tanda_unwrap.id), false);
+        }
+    }
 
-                    self.pagos.insert(&clave, &hist);
-                    env::log(format!("Nuevo pago añadido exitosamente.").as_bytes());
+    pub fn remover_integrante(&mut self, clave: String, cuenta: AccountId) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.autorizar(&clave);
+        self.quitar_integrante_pendiente(clave, cuenta);
+    }
 
-                    true
-                } else {
-                    let mut hist = self.pagos.get(&clave).unwrap();
-                    let mut new_vec = Vec::<Pago>::new();
+    // ! MÉTODO INTERNO
+    fn quitar_integrante_pendiente(&mut self, clave: String, cuenta: AccountId) {
+        let mut tanda = self.tandas.get(&clave).unwrap();
 
-                    new_vec.push(new_payment);
-                    hist.insert(String::from(&id_cuenta), new_vec);
-                    self.pagos.insert(&clave, &hist);
+        assert!(
+            !tanda.activa && tanda.estado == "Pendiente",
+            "Sólo se pueden remover integrantes de Tandas pendientes."
+        );
+        assert!(
+            tanda.integrantes.contains(&cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &cuenta
+        );
 
-                    env::log(format!("El primer pago fue registrado correctamente.").as_bytes());
+        let mut vacios = tanda.quitar_integrante(&cuenta);
+        vacios.sort_unstable();
+
+        // * Se liberan los turnos reservados por el integrante
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        let mut reembolso: u128 = 0;
+        for periodo in periodos.iter_mut() {
+            match periodo.asiento_en_turno {
+                Some(numero) if vacios.contains(&numero) => {
+                    periodo.usuario_en_turno = String::new();
+                    periodo.asiento_en_turno = None;
+                }
+                Some(numero) => {
+                    if periodo.usuario_en_turno == cuenta {
+                        let mut titulares: Vec<&AccountId> = tanda.asientos[numero as usize - 1]
+                            .participaciones
+                            .keys()
+                            .collect();
+                        titulares.sort();
+                        periodo.usuario_en_turno = String::from(titulares[0]);
+                    }
 
-                    true
+                    let recorridos = vacios.iter().filter(|vacio| **vacio < numero).count();
+                    periodo.asiento_en_turno = Some(numero - recorridos as u32);
                 }
+                None => {}
             }
-            None => {
-                env::log(format!("El historial de pagos está vacío.").as_bytes());
-
-                let mut payment_map = HashMap::<String, Vec<Pago>>::new();
-                let mut payment_vec = Vec::<Pago>::new();
-
-                payment_vec.push(new_payment);
-                payment_map.insert(id_cuenta, payment_vec);
-
-                self.pagos.insert(&clave, &payment_map);
 
-                env::log(format!("Se inicializó exitosamente el registro de pagos.").as_bytes());
-
-                true
+            // * Las aportaciones adelantadas se reembolsan, los abonos parciales no cuentan en lo recaudado
+            if let Some(aportado) = periodo.aportaciones.remove(&cuenta) {
+                periodo.integrantes_pagados.remove(&cuenta);
+                periodo.cantidad_recaudada = U128(periodo.cantidad_recaudada.0 - aportado.0);
+                reembolso += aportado.0;
+            }
+            if let Some(abonado) = periodo.abonos_parciales.remove(&cuenta) {
+                reembolso += abonado.0;
             }
         }
-    }
-
-    pub fn consultar_integrante_pagos(
-        &self,
-        clave: String,
-        id_cuenta: Option<String>,
-    ) -> Vec<Pago> {
-        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
-        let valido = self.validar_integrante(String::from(&clave), String::from(&cuenta));
 
-        assert!(valido, "El usuario no es integrante de esta Tanda.");
-        assert!(self.tandas.get(&clave).is_some(), "La Tanda no existe.");
+        for numero in vacios.iter().rev() {
+            tanda.liberar_asiento(*numero);
+        }
 
-        let historial: HashMap<String, Vec<Pago>>;
+        self.tandas.insert(&clave, &tanda);
+        self.periodos_tanda.insert(&clave, &periodos);
 
-        match self.pagos.get(&clave) {
-            Some(_historial) => {
-                historial = self.pagos.get(&clave).unwrap();
-                let hist_user = historial.get(&clave);
+        self.vaciar_monedero(&clave, &cuenta);
+        if reembolso > 0 {
+            self.acreditar_saldo(
+                &cuenta,
+                reembolso,
+                ConceptoMovimiento::Reembolso,
+                String::from(&clave),
+            );
+        }
 
-                if hist_user.is_some() {
-                    hist_user.unwrap().to_vec()
-                } else {
-                    Vec::<Pago>::new()
-                }
+        // * Las ofertas de traspaso del integrante quedan canceladas
+        let mut traspasos = self.traspasos.get(&clave).unwrap_or_default();
+        for traspaso in traspasos.iter_mut() {
+            if traspaso.estado == "Pendiente"
+                && (traspaso.cedente == cuenta || traspaso.cesionario == cuenta)
+            {
+                traspaso.estado = String::from("Cancelado");
+                traspaso.fecha_cierre = Some(date_handling::calcular_inicio());
             }
-            None => Vec::<Pago>::new(),
         }
-    }
+        if !traspasos.is_empty() {
+            self.traspasos.insert(&clave, &traspasos);
+        }
 
-    pub fn consultar_pagos(&self) -> Vec<(String, HashMap<String, Vec<Pago>>)> {
-        self.pagos.to_vec()
+        if let Some(mut usuario) = self.usuarios.get(&cuenta) {
+            usuario.tandas_inscritas.retain(|id| id != &clave);
+            self.usuarios.insert(&cuenta, &usuario);
+        }
+
+        let msg = format!(
+            "El usuario {} fue removido de la Tanda, se liberaron {} asientos.",
+            &cuenta,
+            vacios.len()
+        );
+        log_generator::create_log("ok", "remover_integrante", &clave, &msg);
     }
 
-    pub fn activar_tanda(&mut self, clave: String) -> bool {
-        assert!(clave != "", "El campo de clave no debe estar vacío");
+    pub fn consultar_asientos(&self, clave: String) -> Vec<Asiento> {
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío.");
 
-        match self.tandas.get(&clave) {
-            Some(mut tanda) => {
-                assert!(
-                    tanda.creador == env::predecessor_account_id(),
-                    "No cuentas con permisos para modificar esta Tanda"
-                );
-                assert!(!tanda.activa, "La Tanda ya se encuentra activa.");
-                assert!(
-                    tanda.num_integrantes == tanda.integrantes.len() as u32,
-                    "Hacen falta {} integrantes por unirse",
-                    tanda.num_integrantes - tanda.integrantes.len() as u32
-                );
+        let tanda = self.tandas.get(&clave);
+        assert!(tanda.is_some(), "La tanda no existe.");
 
-                let fecha_hoy = date_handling::calcular_inicio();
+        tanda.unwrap().asientos
+    }
 
-                if tanda.fecha_inicio != fecha_hoy {
-                    tanda.fecha_inicio = fecha_hoy;
-                    tanda.fecha_final = date_handling::agregar_dias(
-                        &date_handling::calcular_inicio(),
-                        (tanda.num_integrantes * tanda.periodo - 1) as i64,
-                    );
+    pub fn consultar_integrantes(&self, clave: String) -> HashSet<AccountId> {
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío.");
 
-                    self.regenerar_periodos(clave.to_string());
-                }
+        let tanda = self.tandas.get(&clave);
+        assert!(tanda.is_some(), "La tanda no existe.");
 
-                tanda.activa = true;
-                tanda.estado = "Activa".to_string();
-                self.tandas.insert(&clave, &tanda);
-                true
-            }
+        tanda.unwrap().integrantes
+    }
+
+    // ! MÉTODO INTERNO
+    fn validar_integrante(&self, id_tanda: String, id_cuenta: AccountId) -> bool {
+        match self.tandas.get(&id_tanda) {
+            Some(tanda) => tanda.integrantes.contains(&id_cuenta),
             None => false,
         }
     }
 
-    pub fn regenerar_periodos(&mut self, clave: String) {
-        assert!(self.tandas.get(&clave).is_some(), "La Tanda no existe");
+    pub fn ofrecer_asiento(
+        &mut self,
+        clave: String,
+        num_asiento: u32,
+        cesionario: AccountId,
+        precio: Option<U128>,
+    ) -> u32 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
         assert!(
-            self.periodos_tanda.get(&clave).is_some(),
-            "Los periodos no están inicializados"
+            tanda.estado != "Finalizada" && tanda.estado != "Disuelta",
+            "La Tanda {} ya no admite traspasos.",
+            &clave
+        );
+        assert!(
+            num_asiento > 0 && num_asiento as usize <= tanda.asientos.len(),
+            "El asiento {} no existe.",
+            num_asiento
+        );
+        assert!(
+            tanda.asientos[num_asiento as usize - 1]
+                .participaciones
+                .contains_key(&id_cuenta),
+            "El usuario {} no ocupa el asiento {}.",
+            &id_cuenta,
+            num_asiento
+        );
+        assert!(
+            cesionario != id_cuenta,
+            "El usuario {} no puede traspasarse el asiento a sí mismo.",
+            &id_cuenta
         );
 
-        let tanda = self.tandas.get(&clave).unwrap();
-        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
-
+        let mut traspasos = self.traspasos.get(&clave).unwrap_or_default();
         assert!(
-            tanda.creador == env::predecessor_account_id(),
-            "No cuentas con permisos para modificar esta Tanda"
+            !traspasos
+                .iter()
+                .any(|traspaso| traspaso.estado == "Pendiente"
+                    && traspaso.asiento == num_asiento
+                    && traspaso.cedente == id_cuenta),
+            "Ya existe una oferta pendiente para el asiento {}.",
+            num_asiento
         );
 
-        let mut inicio_ciclo = tanda.fecha_inicio;
-        let mut final_ciclo: String;
+        let id = traspasos.len() as u32;
+        let precio = precio.map(|precio| precio.0).unwrap_or(0);
+        traspasos.push(Traspaso::new(
+            id,
+            num_asiento,
+            String::from(&cesionario),
+            precio,
+        ));
+        self.traspasos.insert(&clave, &traspasos);
 
-        for n in 0..periodos.len() {
-            periodos[n].inicio = String::from(&inicio_ciclo);
-            final_ciclo = date_handling::agregar_dias(&inicio_ciclo, (tanda.periodo - 1) as i64);
-            periodos[n].fin = final_ciclo.to_string();
+        let msg = format!(
+            "El usuario {} ofreció el asiento {} de la Tanda {} a {} por {} yoctoNEAR.",
+            &id_cuenta, num_asiento, &clave, &cesionario, precio
+        );
+        env::log(msg.as_bytes());
 
-            inicio_ciclo = final_ciclo;
-        }
-
-        self.periodos_tanda.insert(&clave, &periodos);
+        id
     }
 
-    pub fn editar_tanda(
-        &mut self,
-        clave: String,
-        nombre: Option<String>,
-        num_integrantes: Option<u32>,
-        monto: Option<u32>,
-        periodo: Option<u32>,
-        fecha_inicio: Option<String>,
-    ) -> Tanda {
+    #[payable]
+    pub fn aceptar_traspaso(&mut self, clave: String, id: u32) -> Traspaso {
         assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
         let mut tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
 
+        let mut traspasos = self.traspasos.get(&clave).unwrap_or_default();
         assert!(
-            tanda.creador == env::predecessor_account_id(),
-            "No cuentas con autorización para modificar esta Tanda."
+            (id as usize) < traspasos.len(),
+            "El traspaso #{} no existe.",
+            id
         );
 
-        let nombre_unwrap = nombre.unwrap_or("".to_string());
-        if nombre_unwrap != String::new() {
-            tanda.nombre_tanda = nombre_unwrap;
-        }
+        let mut traspaso = traspasos[id as usize].clone();
+        assert!(
+            traspaso.estado == "Pendiente",
+            "El traspaso #{} ya fue cerrado.",
+            id
+        );
+        assert!(
+            traspaso.cesionario == id_cuenta,
+            "El traspaso #{} no está dirigido al usuario {}.",
+            id,
+            &id_cuenta
+        );
+        assert!(
+            env::attached_deposit() == traspaso.precio.0,
+            "El depósito debe ser igual al precio del asiento ({} yoctoNEAR).",
+            traspaso.precio.0
+        );
 
-        if !tanda.activa && tanda.integrantes.len() == 0 {
-            let num_integrantes_unwrap = num_integrantes.unwrap_or(0);
-            let monto_unwrap = monto.unwrap_or(0);
-            let periodo_unwrap = periodo.unwrap_or(0);
-            let fecha_inicio_unwrap = fecha_inicio.unwrap_or(String::new());
+        if !tanda.integrantes.contains(&id_cuenta) {
+            self.validar_ingreso(&tanda, &id_cuenta);
+        }
 
-            if num_integrantes_unwrap != 0 {
-                tanda.num_integrantes = num_integrantes_unwrap;
+        let cedente = String::from(&traspaso.cedente);
+        let numero = traspaso.asiento;
+        let cedida = tanda.traspasar_asiento(numero, &cedente, &id_cuenta);
+        assert!(
+            cedida > 0,
+            "El usuario {} ya no ocupa el asiento {}.",
+            &cedente,
+            numero
+        );
+        self.validar_exposicion(&tanda, &id_cuenta);
+        let sale = !tanda.integrantes.contains(&cedente);
+
+        // * El cesionario hereda el turno y las aportaciones del periodo en curso
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        for (n, periodo) in periodos.iter_mut().enumerate() {
+            if periodo.tanda_pagada {
+                continue;
             }
 
-            if monto_unwrap != 0 {
-                tanda.monto = monto_unwrap;
+            if periodo.asiento_en_turno == Some(numero) && periodo.usuario_en_turno == cedente {
+                periodo.usuario_en_turno = String::from(&id_cuenta);
             }
 
-            if periodo_unwrap != 0 {
-                tanda.periodo = periodo_unwrap;
-            }
+            let aportado = periodo.aportado(&cedente);
+            assert!(
+                aportado == 0 || sale,
+                "El usuario {} conserva otros asientos y ya aportó al periodo en curso.",
+                &cedente
+            );
 
-            if fecha_inicio_unwrap != String::new() {
-                tanda.fecha_inicio = fecha_inicio_unwrap;
+            let total = aportado + periodo.aportado(&id_cuenta);
+            if total == 0 {
+                continue;
             }
 
-            assert!(tanda.num_integrantes > 2, "Número de integrantes no válido");
-            assert!(tanda.monto > 0, "Monto a ahorrar no válido");
+            // * Una cuota ya contada en lo recaudado no puede volver a quedar como abono parcial
+            let cuota = tanda.cuota_monto(&id_cuenta, periodo.monto);
             assert!(
-                tanda.periodo == 7 || tanda.periodo == 15 || tanda.periodo == 30,
-                "Periodo para ahorrar no válido"
+                total >= cuota
+                    || (!periodo.aportaciones.contains_key(&cedente)
+                        && !periodo.aportaciones.contains_key(&id_cuenta)),
+                "El usuario {} y el usuario {} deben completar su aportación al periodo #{} antes del traspaso.",
+                &cedente,
+                &id_cuenta,
+                n + 1
             );
+            periodo.aportaciones.remove(&cedente);
+            periodo.abonos_parciales.remove(&cedente);
+            periodo.integrantes_pagados.remove(&cedente);
+            periodo.aportaciones.remove(&id_cuenta);
+            periodo.abonos_parciales.remove(&id_cuenta);
+            periodo.integrantes_pagados.remove(&id_cuenta);
+
+            if total >= cuota {
+                periodo
+                    .aportaciones
+                    .insert(String::from(&id_cuenta), U128(total));
+                periodo.integrantes_pagados.insert(String::from(&id_cuenta));
+            } else {
+                periodo
+                    .abonos_parciales
+                    .insert(String::from(&id_cuenta), U128(total));
+            }
+        }
 
-            self.tandas.insert(&clave, &tanda);
-            tanda
-        } else {
-            Tanda::default()
+        self.tandas.insert(&clave, &tanda);
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        // * Registro de ambos usuarios
+        if !self
+            .consultar_tandas_inscritas(Some(String::from(&id_cuenta)))
+            .contains(&clave)
+        {
+            self.registrar_usuario(String::from(&id_cuenta), String::from(&clave), false);
+        }
+        if sale {
+            if let Some(mut usuario) = self.usuarios.get(&cedente) {
+                usuario.tandas_inscritas.retain(|tanda| tanda != &clave);
+                self.usuarios.insert(&cedente, &usuario);
+            }
+
+            self.vaciar_monedero(&clave, &cedente);
         }
+
+        if traspaso.precio.0 > 0 {
+            self.acreditar_saldo(
+                &cedente,
+                traspaso.precio.0,
+                ConceptoMovimiento::VentaAsiento,
+                format!("{}#{}", clave, numero),
+            );
+        }
+
+        traspaso.estado = String::from("Aceptado");
+        traspaso.fecha_cierre = Some(date_handling::calcular_inicio());
+        traspasos[id as usize] = traspaso.clone();
+        self.traspasos.insert(&clave, &traspasos);
+
+        let msg = format!(
+            "El usuario {} cedió {}% del asiento {} de la Tanda {} al usuario {}.",
+            &cedente, cedida, numero, &clave, &id_cuenta
+        );
+        env::log(msg.as_bytes());
+
+        self.revisar_periodos(clave);
+        traspaso
     }
 
-    pub fn cancelar_tanda(&mut self, clave: String) -> Tanda {
-        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe");
-        let mut tanda = self.tandas.get(&clave).unwrap();
+    pub fn cancelar_traspaso(&mut self, clave: String, id: u32) -> Traspaso {
+        let id_cuenta = env::predecessor_account_id();
+        let mut traspasos = self.traspasos.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < traspasos.len(),
+            "El traspaso #{} no existe.",
+            id
+        );
 
+        let traspaso = &mut traspasos[id as usize];
         assert!(
-            tanda.creador == env::predecessor_account_id(),
-            "No cuentas con autorización para modificar esta Tanda."
+            traspaso.estado == "Pendiente",
+            "El traspaso #{} ya fue cerrado.",
+            id
         );
         assert!(
-            self.pagos.get(&clave).is_some(),
-            "Esta Tanda ya se encuentra en progreso, no se puede cancelar."
+            traspaso.cedente == id_cuenta || traspaso.cesionario == id_cuenta,
+            "El usuario {} no participa en el traspaso #{}.",
+            &id_cuenta,
+            id
         );
 
-        tanda.activa = false;
-        tanda.estado = "Cancelada".to_string();
-        self.tandas.insert(&clave, &tanda);
-        tanda
+        traspaso.estado = String::from("Cancelado");
+        traspaso.fecha_cierre = Some(date_handling::calcular_inicio());
+        let traspaso = traspaso.clone();
+        self.traspasos.insert(&clave, &traspasos);
+
+        traspaso
     }
 
-    pub fn validar_pago_tanda(&mut self, clave: String, indice: i32) -> bool {
+    pub fn consultar_traspasos(&self, clave: String) -> Vec<Traspaso> {
         assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.traspasos.get(&clave).unwrap_or_default()
+    }
+
+    #[payable]
+    pub fn agregar_integrante_pago(&mut self, clave: String) -> bool {
+        // * Validaciones
+        let tanda_check = self.tandas.get(&clave);
+        assert!(tanda_check.is_some(), "La tanda no existe.");
+        let tanda = tanda_check.unwrap();
+        let monto = env::attached_deposit();
+
+        assert!(!tanda.pausada, "La Tanda se encuentra pausada.");
+
+        let id_cuenta = env::predecessor_account_id();
+        let valido = self.validar_integrante(String::from(&tanda.id), String::from(&id_cuenta));
+
+        assert!(
+            valido,
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
 
         assert!(
             self.periodos_tanda.get(&clave).is_some(),
             "Los periodos para esta tanda no están inicializados."
         );
 
-        let tanda = self.tandas.get(&clave).unwrap();
-        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
-        let i = indice as usize;
-        let cantidad_a_pagar = tanda.monto * tanda.num_integrantes;
+        self.cobrar_monedero(String::from(&clave), String::from(&id_cuenta));
 
-        if periodos[i].cantidad_recaudada == cantidad_a_pagar
-            && periodos[i].integrantes_pagados.len() as u32 == tanda.num_integrantes
-        {
-            periodos[i].pagos_completos = true;
-            self.periodos_tanda.insert(&clave, &periodos);
+        let indice = self.validar_periodo(String::from(&clave), Some(String::from(&id_cuenta)));
 
-            true
-        } else {
-            false
-        }
+        assert!(
+            indice >= 0,
+            "El usuario {} no puede realizar pagos. 
+            Ya se realizaron todos los pagos correspondientes a esta Tanda",
+            &id_cuenta
+        );
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+        assert!(
+            !periodos[indice as usize]
+                .abonos_parciales
+                .contains_key(&id_cuenta),
+            "El usuario {} tiene abonos parciales en el periodo #{}, debe completarlo con abonar_periodo.",
+            &id_cuenta,
+            indice + 1
+        );
+
+        let cuota = tanda.adeudo_total(&id_cuenta, &periodos[indice as usize]);
+        assert!(
+            monto == cuota,
+            "Sólo se pueden realizar pagos por la cantidad establecida en la Tanda ({} yoctoNEAR).",
+            cuota
+        );
+
+        self.registrar_aportacion(clave, id_cuenta, indice as usize)
     }
 
-    pub fn escoger_turno(&mut self, clave: String, num_turno: usize) {
+    #[payable]
+    pub fn agregar_integrante_pago_multiple(&mut self, clave: String, num_periodos: u32) -> bool {
+        // * Validaciones
         assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let monto = env::attached_deposit();
+
+        assert!(num_periodos > 0, "Se debe pagar al menos un periodo.");
+        assert!(!tanda.pausada, "La Tanda se encuentra pausada.");
+
         let id_cuenta = env::predecessor_account_id();
         let valido = self.validar_integrante(String::from(&clave), String::from(&id_cuenta));
+
         assert!(
             valido,
             "El usuario {} no es integrante de esta tanda.",
@@ -518,153 +1030,4772 @@ impl TandaDapp {
 
         assert!(
             self.periodos_tanda.get(&clave).is_some(),
-            "Los periodos no están inicializados"
+            "Los periodos para esta tanda no están inicializados."
         );
 
-        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        self.cobrar_monedero(String::from(&clave), String::from(&id_cuenta));
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+        let pendientes = periodos
+            .iter()
+            .filter(|periodo| !periodo.integrantes_pagados.contains(&id_cuenta))
+            .count() as u32;
 
         assert!(
-            num_turno <= periodos.len() && num_turno > 0,
-            "La tanda sólo contiene {} espacios.",
-            periodos.len()
+            num_periodos <= pendientes,
+            "El usuario {} sólo tiene {} periodos pendientes de pago.",
+            &id_cuenta,
+            pendientes
         );
-
         assert!(
-            periodos[num_turno - 1].usuario_en_turno == String::new(),
-            "El turno {} ya está tomado por {}",
-            num_turno,
-            periodos[num_turno - 1].usuario_en_turno
+            periodos
+                .iter()
+                .all(|periodo| !periodo.abonos_parciales.contains_key(&id_cuenta)),
+            "El usuario {} tiene abonos parciales pendientes, debe completarlos con abonar_periodo.",
+            &id_cuenta
         );
 
-        periodos[num_turno - 1].usuario_en_turno = env::predecessor_account_id();
+        let total: u128 = periodos
+            .iter()
+            .filter(|periodo| !periodo.integrantes_pagados.contains(&id_cuenta))
+            .take(num_periodos as usize)
+            .map(|periodo| tanda.adeudo_total(&id_cuenta, periodo))
+            .sum();
+        assert!(
+            monto == total,
+            "Para pagar {} periodos se requieren exactamente {} yoctoNEAR.",
+            num_periodos,
+            total
+        );
 
-        self.periodos_tanda.insert(&clave, &periodos);
+        // * Registro de periodos consecutivos
+        for _n in 0..num_periodos {
+            let indice = self.validar_periodo(String::from(&clave), Some(String::from(&id_cuenta)));
+            self.registrar_aportacion(
+                String::from(&clave),
+                String::from(&id_cuenta),
+                indice as usize,
+            );
+        }
 
         let msg = format!(
-            "El usuario {} ha tomado exitosamente el turno {} en la Tanda.",
-            env::predecessor_account_id(),
-            num_turno
+            "El usuario {} pagó {} periodos de la Tanda {}.",
+            &id_cuenta, num_periodos, &clave
         );
         env::log(msg.as_bytes());
+
+        true
     }
 
-    pub fn validar_periodo(&self, clave: String, id_cuenta: Option<String>) -> i32 {
+    #[payable]
+    pub fn abonar_periodo(&mut self, clave: String) -> U128 {
+        // * Validaciones
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+
         assert!(
-            self.periodos_tanda.get(&clave).is_some(),
-            "Los periodos para esta tanda no están inicializados."
+            tanda.pagos_parciales,
+            "Esta Tanda no acepta pagos parciales."
         );
+        assert!(!tanda.pausada, "La Tanda se encuentra pausada.");
 
-        let periodos = self.periodos_tanda.get(&clave).unwrap();
-        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+        let id_cuenta = env::predecessor_account_id();
+        let valido = self.validar_integrante(String::from(&clave), String::from(&id_cuenta));
 
-        for n in 0..periodos.len() {
-            if !periodos[n].integrantes_pagados.contains(&cuenta) {
-                return n as i32;
-            }
-        }
+        assert!(
+            valido,
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
 
-        -1
-    }
+        let abono = env::attached_deposit();
+        assert!(abono > 0, "El abono tiene que ser mayor a 0.");
 
-    pub fn generar_periodos(&mut self, clave: String) {
-        match self.tandas.get(&clave) {
-            Some(tanda) => {
-                match self.periodos_tanda.get(&clave) {
-                    Some(_periodos) => {
-                        // generar log de que ya estaban inicializados..
-                    }
-                    None => {
-                        //Checar errores...
-                        // TODO: Check borrowing
-                        let mut vec_periodos = Vec::<Periodo>::new();
-                        let mut fecha_inicio = &tanda.fecha_inicio;
-                        let mut temp: String;
-
-                        for _n in 0..tanda.num_integrantes {
-                            let fecha_final = date_handling::agregar_dias(
-                                &fecha_inicio,
-                                (tanda.periodo - 1) as i64,
-                            );
-                            let periodo: Periodo = Periodo::new(
-                                String::from(fecha_inicio),
-                                String::from(&fecha_final),
-                            );
-
-                            vec_periodos.push(periodo);
-
-                            temp = date_handling::agregar_dias(&String::from(fecha_final), 1);
-                            fecha_inicio = &temp;
-                        }
-
-                        self.periodos_tanda.insert(&clave, &vec_periodos);
-                    }
-                }
-            }
-            None => {}
-        }
-    }
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos para esta tanda no están inicializados."
+        );
 
-    pub fn consultar_periodos(&self, clave: String) -> Option<Vec<Periodo>> {
-        self.periodos_tanda.get(&clave)
-    }
+        self.cobrar_monedero(String::from(&clave), String::from(&id_cuenta));
 
-    pub fn obtener_periodo_a_pagar(&self, clave: String) -> i32 {
+        let indice = self.validar_periodo(String::from(&clave), Some(String::from(&id_cuenta)));
         assert!(
-            clave != String::new(),
-            "El campo clave no debe estar vacío."
+            indice >= 0,
+            "El usuario {} ya realizó todos los pagos correspondientes a esta Tanda.",
+            &id_cuenta
         );
-        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe");
+
+        let n = indice as usize;
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        let cuota = tanda.cuota_monto(&id_cuenta, periodos[n].monto);
+        let adeudo = tanda.adeudo_total(&id_cuenta, &periodos[n]);
+        let adeudo_cuota = periodos[n].adeudo(&id_cuenta, cuota);
+
         assert!(
-            self.periodos_tanda.get(&clave).is_some(),
-            "Los periodos para esta tanda no están inicializados."
+            abono <= adeudo,
+            "El abono excede el adeudo del periodo #{} ({} yoctoNEAR).",
+            n + 1,
+            adeudo
+        );
+        assert!(
+            abono == adeudo || abono < adeudo_cuota,
+            "El abono que completa la cuota debe cubrir también la penalización ({} yoctoNEAR).",
+            adeudo
         );
 
-        let periodos = self.periodos_tanda.get(&clave).unwrap();
-
-        for n in 0..periodos.len() {
-            if periodos[n].tanda_pagada == false {
-                return n as i32;
-            }
+        // * Registro del abono
+        if abono == adeudo {
+            self.registrar_aportacion(clave, String::from(&id_cuenta), n);
+        } else {
+            periodos[n]
+                .abonos_parciales
+                .insert(String::from(&id_cuenta), U128(cuota - adeudo_cuota + abono));
+            self.periodos_tanda.insert(&clave, &periodos);
         }
-        -1
-    }
 
-    pub fn pagar_tanda(&mut self, clave: String, indice: i32) -> bool {
-        assert!(
-            clave != String::new(),
-            "El campo clave no debe estar vacío."
+        let msg = format!(
+            "El usuario {} abonó {} yoctoNEAR al periodo #{}, restan {} yoctoNEAR.",
+            &id_cuenta,
+            abono,
+            n + 1,
+            adeudo - abono
         );
-        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe");
+        env::log(msg.as_bytes());
+
+        U128(adeudo - abono)
+    }
+
+    pub fn consultar_adeudos(&self, clave: String, id_cuenta: Option<String>) -> Vec<U128> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
         assert!(
             self.periodos_tanda.get(&clave).is_some(),
             "Los periodos para esta tanda no están inicializados."
         );
 
-        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
-        let n = indice as usize;
+        let tanda = self.tandas.get(&clave).unwrap();
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
 
-        assert!(
-            periodos[n].pagos_completos == true,
-            "Este periodo aún no puede ser pagado."
-        );
+        self.periodos_tanda
+            .get(&clave)
+            .unwrap()
+            .iter()
+            .map(|periodo| U128(tanda.adeudo_total(&cuenta, periodo)))
+            .collect()
+    }
 
-        env::log(format!("Validando que este periodo pueda ser pagado...").as_bytes());
-        assert!(
-            periodos[n].usuario_en_turno != String::new(),
-            "No hay usuario en turno en este periodo."
+    // ! MÉTODO INTERNO
+    fn registrar_aportacion(&mut self, clave: String, id_cuenta: AccountId, indice: usize) -> bool {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        let penalizacion = tanda.penalizacion_monto(&id_cuenta, &periodos[indice]);
+        let reserva = tanda.reserva_monto(&id_cuenta, &periodos[indice]);
+        let cuota = tanda.cuota_monto(&id_cuenta, periodos[indice].monto) + penalizacion;
+
+        // * Registro en historial de pagos
+        let mut new_payment = Pago::new(
+            String::from(&id_cuenta),
+            indice as u32,
+            cuota + reserva,
+            &periodos[indice],
         );
+        new_payment.penalizacion = U128(penalizacion);
+        new_payment.reserva = U128(reserva);
+
+        // * La parte de la reserva no entra al periodo
+        if reserva > 0 {
+            let mut fondo = self.reservas.get(&clave).unwrap_or_default();
+            fondo.aportar(&id_cuenta, reserva);
+            self.reservas.insert(&clave, &fondo);
+        }
 
-        let monto = one_near()
-            .checked_mul(periodos[n].cantidad_recaudada as u128)
-            .unwrap_or(0);
-        Promise::new(env::predecessor_account_id()).transfer(monto);
+        // * Registro en periodos
+        periodos[indice]
+            .integrantes_pagados
+            .insert(String::from(&id_cuenta));
+        periodos[indice].cantidad_recaudada = U128(periodos[indice].cantidad_recaudada.0 + cuota);
+        periodos[indice].abonos_parciales.remove(&id_cuenta);
+        periodos[indice]
+            .aportaciones
+            .insert(String::from(&id_cuenta), U128(cuota));
 
-        periodos[n].tanda_pagada = true;
         self.periodos_tanda.insert(&clave, &periodos);
 
-        let msg = format!("La Tanda fue pagada exitosamente. El usuario {} recibió {} NEAR correspondientes al periodo #{}.", periodos[n].usuario_en_turno, periodos[n].cantidad_recaudada, {indice + 1});
-        env::log(msg.as_bytes());
-
-        true
+        self.completar_periodo(String::from(&clave), indice);
+        self.registrar_pago(clave, id_cuenta, new_payment)
     }
-}
+
+    // ! MÉTODO INTERNO
+    fn completar_periodo(&mut self, clave: String, indice: usize) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let periodo_completo = self.validar_pago_tanda(String::from(&clave), indice as i32);
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+
+        // * Pago automático al completar el periodo, las cajas de ahorro sólo pagan al vencimiento
+        if periodo_completo && tanda.pago_automatico && !tanda.es_caja_ahorro() {
+            if self.tanda_congelada(&clave) {
+                env::log(
+                    "El periodo está completo, pero los pagos están congelados por una disputa."
+                        .as_bytes(),
+                );
+            } else if !self.fondos_disponibles(&clave, indice) {
+                env::log(
+                    "El periodo está completo, se pagará al recuperar sus fondos del pool de staking."
+                        .as_bytes(),
+                );
+            } else if periodos[indice].usuario_en_turno == String::new() {
+                env::log(
+                    format!(
+                        "El periodo #{} está completo pero no tiene usuario en turno, se deberá pagar manualmente.",
+                        indice + 1
+                    )
+                    .as_bytes(),
+                );
+            } else {
+                self.acreditar_periodo(String::from(&clave), indice);
+            }
+        }
+
+        self.registrar_morosos(&clave);
+        self.gestionar_staking(&clave);
+    }
+
+    // ! MÉTODO INTERNO
+    fn registrar_pago(
+        &mut self,
+        clave: String,
+        id_cuenta: AccountId,
+        mut new_payment: Pago,
+    ) -> bool {
+        self.limpiar_incumplimiento(&clave, &id_cuenta);
+
+        let num_recibos: usize = self
+            .pagos
+            .get(&clave)
+            .map(|historial| historial.values().map(|pagos| pagos.len()).sum())
+            .unwrap_or(0);
+        new_payment.id = format!("{}-{}", &clave, num_recibos + 1);
+
+        match self.pagos.get(&clave) {
+            Some(mut historial) => {
+                if historial.get_mut(&id_cuenta).is_some() {
+                    let hist_user = historial.get_mut(&id_cuenta).unwrap();
+                    let mut hist = self.pagos.get(&clave).unwrap();
+
+                    hist_user.push(new_payment);
+                    hist.insert(String::from(&id_cuenta), hist_user.to_vec());
+
+                    self.pagos.insert(&clave, &hist);
+                    env::log("Nuevo pago añadido exitosamente.".as_bytes());
+
+                    true
+                } else {
+                    let mut hist = self.pagos.get(&clave).unwrap();
+                    let new_vec = vec![new_payment];
+
+                    hist.insert(String::from(&id_cuenta), new_vec);
+                    self.pagos.insert(&clave, &hist);
+
+                    env::log("El primer pago fue registrado correctamente.".as_bytes());
+
+                    true
+                }
+            }
+            None => {
+                env::log("El historial de pagos está vacío.".as_bytes());
+
+                let mut payment_map = HashMap::<String, Vec<Pago>>::new();
+                let payment_vec = vec![new_payment];
+
+                payment_map.insert(id_cuenta, payment_vec);
+
+                self.pagos.insert(&clave, &payment_map);
+
+                env::log("Se inicializó exitosamente el registro de pagos.".as_bytes());
+
+                true
+            }
+        }
+    }
+
+    pub fn registrar_pago_externo(&mut self, clave: String, indice: u32, cuenta: AccountId) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.es_administrador(&id_cuenta),
+            "No cuentas con autorización para modificar esta Tanda."
+        );
+        assert!(
+            tanda.integrantes.contains(&cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &cuenta
+        );
+
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        let n = indice as usize;
+        assert!(n < periodos.len(), "El periodo #{} no existe.", n + 1);
+        assert!(!periodos[n].tanda_pagada, "Este periodo ya fue pagado.");
+        assert!(
+            !periodos[n].integrantes_pagados.contains(&cuenta)
+                && !periodos[n].externos_por_confirmar.contains_key(&cuenta),
+            "El pago de {} en el periodo #{} ya fue registrado.",
+            &cuenta,
+            n + 1
+        );
+
+        // * Confirman el integrante y, si la Tanda lo pide, quien recibe el turno
+        let mut confirmantes = HashSet::new();
+        confirmantes.insert(String::from(&cuenta));
+
+        let receptor = &periodos[n].usuario_en_turno;
+        if tanda.confirmar_receptor && !receptor.is_empty() && receptor != &cuenta {
+            confirmantes.insert(String::from(receptor));
+        }
+        if id_cuenta != cuenta {
+            confirmantes.remove(&id_cuenta);
+        }
+
+        let pendientes = confirmantes.len();
+        periodos[n]
+            .externos_por_confirmar
+            .insert(String::from(&cuenta), confirmantes);
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        let msg = format!(
+            "Se registró un pago en efectivo de {} en el periodo #{}, faltan {} confirmaciones.",
+            &cuenta,
+            n + 1,
+            pendientes
+        );
+        log_generator::create_log("ok", "registrar_pago_externo", &clave, &msg);
+
+        if pendientes == 0 {
+            self.acreditar_pago_externo(clave, cuenta, n);
+        }
+    }
+
+    pub fn confirmar_pago_externo(
+        &mut self,
+        clave: String,
+        indice: u32,
+        cuenta: AccountId,
+        confirmar: bool,
+    ) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let id_cuenta = env::predecessor_account_id();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        let n = indice as usize;
+        assert!(n < periodos.len(), "El periodo #{} no existe.", n + 1);
+
+        let confirmantes = periodos[n].externos_por_confirmar.get_mut(&cuenta);
+        assert!(
+            confirmantes.is_some(),
+            "No hay un pago en efectivo de {} por confirmar en el periodo #{}.",
+            &cuenta,
+            n + 1
+        );
+
+        let confirmantes = confirmantes.unwrap();
+        assert!(
+            confirmantes.remove(&id_cuenta),
+            "El usuario {} no tiene que confirmar este pago.",
+            &id_cuenta
+        );
+        let completo = confirmantes.is_empty();
+
+        if !confirmar {
+            periodos[n].externos_por_confirmar.remove(&cuenta);
+        }
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        let msg = format!(
+            "El usuario {} {} el pago en efectivo de {} en el periodo #{}.",
+            &id_cuenta,
+            if confirmar { "confirmó" } else { "rechazó" },
+            &cuenta,
+            n + 1
+        );
+        log_generator::create_log("ok", "confirmar_pago_externo", &clave, &msg);
+
+        if confirmar && completo {
+            self.acreditar_pago_externo(clave, cuenta, n);
+        }
+    }
+
+    // ! MÉTODO INTERNO
+    fn acreditar_pago_externo(&mut self, clave: String, cuenta: AccountId, indice: usize) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        let cuota = tanda.cuota_monto(&cuenta, periodos[indice].monto);
+
+        periodos[indice].externos_por_confirmar.remove(&cuenta);
+        periodos[indice]
+            .pagos_externos
+            .insert(String::from(&cuenta));
+        periodos[indice]
+            .integrantes_pagados
+            .insert(String::from(&cuenta));
+        let abonado = periodos[indice].abonos_parciales.remove(&cuenta);
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        // * Lo abonado en el contrato se devuelve, la cuota se cubrió en efectivo
+        if let Some(abonado) = abonado {
+            self.acreditar_saldo(
+                &cuenta,
+                abonado.0,
+                ConceptoMovimiento::Reembolso,
+                format!("{}#{}", clave, indice + 1),
+            );
+        }
+
+        let recibo = Pago::fuera_de_cadena(
+            String::from(&cuenta),
+            indice as u32,
+            cuota,
+            &periodos[indice],
+        );
+
+        self.completar_periodo(String::from(&clave), indice);
+        self.registrar_pago(clave, cuenta, recibo);
+    }
+
+    #[payable]
+    pub fn depositar_monedero(&mut self, clave: String) -> EstadoMonedero {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let id_cuenta = env::predecessor_account_id();
+        let valido = self.validar_integrante(String::from(&clave), String::from(&id_cuenta));
+
+        assert!(
+            valido,
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+
+        let deposito = env::attached_deposit();
+        assert!(deposito > 0, "El depósito tiene que ser mayor a 0.");
+
+        let mut monederos = self.monederos.get(&clave).unwrap_or_default();
+        let saldo = monederos.get(&id_cuenta).copied().unwrap_or(0);
+        monederos.insert(String::from(&id_cuenta), saldo + deposito);
+        self.monederos.insert(&clave, &monederos);
+
+        let msg = format!(
+            "El usuario {} depositó {} yoctoNEAR en su monedero de la Tanda {}.",
+            &id_cuenta, deposito, &clave
+        );
+        env::log(msg.as_bytes());
+
+        self.cobrar_monedero(String::from(&clave), String::from(&id_cuenta));
+        self.consultar_monedero(clave, Some(id_cuenta))
+    }
+
+    pub fn retirar_monedero(&mut self, clave: String, monto: Option<U128>) -> EstadoMonedero {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let id_cuenta = env::predecessor_account_id();
+
+        // Las aportaciones de periodos ya iniciados se cobran antes de liberar fondos.
+        self.cobrar_monedero(String::from(&clave), String::from(&id_cuenta));
+
+        let mut monederos = self.monederos.get(&clave).unwrap_or_default();
+        let saldo = monederos.get(&id_cuenta).copied().unwrap_or(0);
+        let monto_retiro = monto.map(|m| m.0).unwrap_or(saldo);
+
+        assert!(
+            monto_retiro > 0,
+            "No tienes fondos disponibles en el monedero."
+        );
+        assert!(
+            monto_retiro <= saldo,
+            "Fondos insuficientes, el monedero sólo tiene {} yoctoNEAR.",
+            saldo
+        );
+
+        if saldo == monto_retiro {
+            monederos.remove(&id_cuenta);
+        } else {
+            monederos.insert(String::from(&id_cuenta), saldo - monto_retiro);
+        }
+        self.monederos.insert(&clave, &monederos);
+
+        self.acreditar_saldo(
+            &id_cuenta,
+            monto_retiro,
+            ConceptoMovimiento::ReembolsoMonedero,
+            String::from(&clave),
+        );
+
+        self.consultar_monedero(clave, Some(id_cuenta))
+    }
+
+    pub fn cobrar_monederos(&mut self, clave: String) -> u32 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+
+        tanda
+            .integrantes
+            .iter()
+            .map(|integrante| self.cobrar_monedero(String::from(&clave), String::from(integrante)))
+            .sum()
+    }
+
+    // ! MÉTODO INTERNO
+    fn cobrar_monedero(&mut self, clave: String, id_cuenta: AccountId) -> u32 {
+        let tanda = match self.tandas.get(&clave) {
+            Some(tanda) if tanda.activa && !tanda.pausada => tanda,
+            _ => return 0,
+        };
+
+        let mut monederos = match self.monederos.get(&clave) {
+            Some(monederos) => monederos,
+            None => return 0,
+        };
+
+        let mut saldo = monederos.get(&id_cuenta).copied().unwrap_or(0);
+        let mut cobrados = 0;
+
+        loop {
+            let indice = self.validar_periodo(String::from(&clave), Some(String::from(&id_cuenta)));
+            if indice < 0 {
+                break;
+            }
+
+            let periodos = self.periodos_tanda.get(&clave).unwrap();
+            let adeudo = tanda.adeudo_total(&id_cuenta, &periodos[indice as usize]);
+            if saldo < adeudo || !date_handling::fecha_alcanzada(&periodos[indice as usize].inicio)
+            {
+                break;
+            }
+
+            saldo -= adeudo;
+            self.registrar_aportacion(
+                String::from(&clave),
+                String::from(&id_cuenta),
+                indice as usize,
+            );
+            cobrados += 1;
+        }
+
+        if cobrados > 0 {
+            monederos.insert(String::from(&id_cuenta), saldo);
+            self.monederos.insert(&clave, &monederos);
+
+            let msg = format!(
+                "Se cobraron {} periodos del monedero de {} en la Tanda {}.",
+                cobrados, &id_cuenta, &clave
+            );
+            env::log(msg.as_bytes());
+        }
+
+        cobrados
+    }
+
+    // ! MÉTODO INTERNO
+    // Los fondos prepagados de quien deja la Tanda regresan a su saldo.
+    fn vaciar_monedero(&mut self, clave: &String, cuenta: &AccountId) {
+        let mut monederos = self.monederos.get(clave).unwrap_or_default();
+        if let Some(prepagado) = monederos.remove(cuenta) {
+            self.monederos.insert(clave, &monederos);
+            self.acreditar_saldo(
+                cuenta,
+                prepagado,
+                ConceptoMovimiento::ReembolsoMonedero,
+                String::from(clave),
+            );
+        }
+    }
+
+    pub fn consultar_monedero(&self, clave: String, id_cuenta: Option<String>) -> EstadoMonedero {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+
+        let saldo = self
+            .monederos
+            .get(&clave)
+            .and_then(|monederos| monederos.get(&cuenta).copied())
+            .unwrap_or(0);
+
+        EstadoMonedero {
+            saldo: U128(saldo),
+            periodos_prepagados: match tanda.cuota(&cuenta) {
+                0 => 0,
+                cuota => (saldo / cuota) as u32,
+            },
+        }
+    }
+
+    pub fn consultar_integrante_pagos(
+        &self,
+        clave: String,
+        id_cuenta: Option<String>,
+    ) -> Vec<Pago> {
+        assert!(self.tandas.get(&clave).is_some(), "La Tanda no existe.");
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+
+        match self.pagos.get(&clave) {
+            Some(historial) => historial.get(&cuenta).cloned().unwrap_or_default(),
+            None => Vec::<Pago>::new(),
+        }
+    }
+
+    pub fn consultar_recibos_periodo(&self, clave: String, indice: u32) -> Vec<Pago> {
+        assert!(self.tandas.get(&clave).is_some(), "La Tanda no existe.");
+
+        let mut recibos: Vec<Pago> = self
+            .pagos
+            .get(&clave)
+            .unwrap_or_default()
+            .into_values()
+            .flatten()
+            .filter(|pago| pago.indice_periodo == indice)
+            .collect();
+        recibos.sort_by_key(|pago| pago.timestamp.0);
+        recibos
+    }
+
+    pub fn consultar_pagos(&self) -> Vec<(String, HashMap<String, Vec<Pago>>)> {
+        self.pagos.to_vec()
+    }
+
+    pub fn activar_tanda(&mut self, clave: String) -> bool {
+        assert!(!clave.is_empty(), "El campo de clave no debe estar vacío");
+
+        if self.tandas.get(&clave).is_none() {
+            return false;
+        }
+
+        self.autorizar(&clave);
+        self.activar(clave)
+    }
+
+    // ! MÉTODO INTERNO
+    fn activar(&mut self, clave: String) -> bool {
+        match self.tandas.get(&clave) {
+            Some(mut tanda) => {
+                assert!(!tanda.activa, "La Tanda ya se encuentra activa.");
+                assert!(
+                    tanda.num_integrantes == tanda.asientos.len() as u32,
+                    "Hacen falta {} integrantes por unirse",
+                    tanda.num_integrantes - tanda.asientos.len() as u32
+                );
+                assert!(
+                    tanda.asientos_completos(),
+                    "Hay asientos compartidos con participaciones incompletas."
+                );
+
+                let fecha_hoy = date_handling::calcular_inicio();
+
+                if tanda.fecha_inicio != fecha_hoy {
+                    tanda.fecha_inicio = fecha_hoy;
+                    tanda.actualizar_fecha_final();
+
+                    self.regenerar(clave.to_string());
+                }
+
+                tanda.activa = true;
+                tanda.estado = "Activa".to_string();
+                self.tandas.insert(&clave, &tanda);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn regenerar_periodos(&mut self, clave: String) {
+        assert!(self.tandas.get(&clave).is_some(), "La Tanda no existe");
+
+        self.autorizar(&clave);
+        self.regenerar(clave);
+    }
+
+    // ! MÉTODO INTERNO
+    fn regenerar(&mut self, clave: String) {
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos no están inicializados"
+        );
+
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+
+        let mut inicio_ciclo = tanda.fecha_inicio;
+        let mut final_ciclo: String;
+
+        for periodo in periodos.iter_mut() {
+            periodo.inicio = String::from(&inicio_ciclo);
+            final_ciclo = date_handling::agregar_dias(&inicio_ciclo, (tanda.periodo - 1) as i64);
+            periodo.fin = final_ciclo.to_string();
+
+            inicio_ciclo = final_ciclo;
+        }
+
+        self.periodos_tanda.insert(&clave, &periodos);
+    }
+
+    pub fn editar_tanda(
+        &mut self,
+        clave: String,
+        nombre: Option<String>,
+        num_integrantes: Option<u32>,
+        monto: Option<u32>,
+        periodo: Option<u32>,
+        fecha_inicio: Option<String>,
+    ) -> Tanda {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.autorizar(&clave);
+        self.editar(clave, nombre, num_integrantes, monto, periodo, fecha_inicio)
+    }
+
+    // ! MÉTODO INTERNO
+    fn editar(
+        &mut self,
+        clave: String,
+        nombre: Option<String>,
+        num_integrantes: Option<u32>,
+        monto: Option<u32>,
+        periodo: Option<u32>,
+        fecha_inicio: Option<String>,
+    ) -> Tanda {
+        let mut tanda = self.tandas.get(&clave).unwrap();
+
+        let nombre_unwrap = nombre.unwrap_or("".to_string());
+        if nombre_unwrap != String::new() {
+            tanda.nombre_tanda = nombre_unwrap;
+        }
+
+        if !tanda.activa && tanda.integrantes.is_empty() {
+            let num_integrantes_unwrap = num_integrantes.unwrap_or(0);
+            let monto_unwrap = monto.unwrap_or(0);
+            let periodo_unwrap = periodo.unwrap_or(0);
+            let fecha_inicio_unwrap = fecha_inicio.unwrap_or_default();
+
+            if num_integrantes_unwrap != 0 {
+                tanda.num_integrantes = num_integrantes_unwrap;
+            }
+
+            if monto_unwrap != 0 {
+                tanda.monto = monto_unwrap;
+            }
+
+            if periodo_unwrap != 0 {
+                tanda.periodo = periodo_unwrap;
+            }
+
+            if fecha_inicio_unwrap != String::new() {
+                tanda.fecha_inicio = fecha_inicio_unwrap;
+            }
+
+            assert!(tanda.num_integrantes > 2, "Número de integrantes no válido");
+            assert!(tanda.monto > 0, "Monto a ahorrar no válido");
+            assert!(
+                tanda.periodo == 7 || tanda.periodo == 15 || tanda.periodo == 30,
+                "Periodo para ahorrar no válido"
+            );
+
+            self.tandas.insert(&clave, &tanda);
+            tanda
+        } else {
+            Tanda::default()
+        }
+    }
+
+    pub fn configurar_tanda(&mut self, clave: String, opciones: OpcionesTanda) -> Tanda {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.autorizar(&clave);
+        self.validar_cambio_pool(&clave, &opciones);
+
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        tanda.aplicar_opciones(opciones);
+        self.tandas.insert(&clave, &tanda);
+        tanda
+    }
+
+    pub fn cancelar_tanda(&mut self, clave: String) -> Tanda {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe");
+        self.autorizar(&clave);
+        self.cancelar(clave)
+    }
+
+    // ! MÉTODO INTERNO
+    fn cancelar(&mut self, clave: String) -> Tanda {
+        let mut tanda = self.tandas.get(&clave).unwrap();
+
+        assert!(
+            self.pagos.get(&clave).is_none(),
+            "Esta Tanda ya se encuentra en progreso, no se puede cancelar. Debe disolverse mediante una propuesta."
+        );
+        assert!(
+            self.prestamos
+                .get(&clave)
+                .map_or(0, |cartera| cartera.prestado(&[]))
+                == 0,
+            "Esta Tanda tiene préstamos vigentes, no se puede cancelar. Debe disolverse mediante una propuesta."
+        );
+
+        tanda.activa = false;
+        tanda.estado = "Cancelada".to_string();
+        self.tandas.insert(&clave, &tanda);
+        tanda
+    }
+
+    // * ORGANIZADORES
+
+    // ! MÉTODO INTERNO
+    fn autorizar(&self, clave: &String) {
+        let tanda = self.tandas.get(clave).unwrap();
+
+        assert!(
+            tanda.es_administrador(&env::predecessor_account_id()),
+            "No cuentas con autorización para modificar esta Tanda."
+        );
+        assert!(
+            tanda.aprobaciones <= 1,
+            "Esta acción requiere {} aprobaciones, debe solicitarse con solicitar_accion.",
+            tanda.aprobaciones
+        );
+    }
+
+    pub fn agregar_organizador(&mut self, clave: String, cuenta: AccountId, rol: RolOrganizador) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.autorizar(&clave);
+        self.ejecutar_accion(
+            clave,
+            AccionAdministrativa::AgregarOrganizador { cuenta, rol },
+        );
+    }
+
+    pub fn quitar_organizador(&mut self, clave: String, cuenta: AccountId) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.autorizar(&clave);
+        self.ejecutar_accion(clave, AccionAdministrativa::QuitarOrganizador { cuenta });
+    }
+
+    pub fn consultar_organizadores(&self, clave: String) -> Vec<(AccountId, RolOrganizador)> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+
+        let mut organizadores = vec![(String::from(&tanda.creador), RolOrganizador::Administrador)];
+        organizadores.extend(tanda.organizadores);
+        organizadores
+    }
+
+    pub fn solicitar_accion(&mut self, clave: String, accion: AccionAdministrativa) -> u32 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.rol(&id_cuenta).is_some(),
+            "El usuario {} no es organizador de esta Tanda.",
+            &id_cuenta
+        );
+
+        let mut solicitudes = self.solicitudes.get(&clave).unwrap_or_default();
+        let id = solicitudes.len() as u32;
+        let mut solicitud = SolicitudAdministrativa::new(id, accion);
+
+        if tanda.es_administrador(&id_cuenta) {
+            solicitud.aprobaciones.insert(String::from(&id_cuenta));
+        }
+        solicitudes.push(solicitud);
+        self.solicitudes.insert(&clave, &solicitudes);
+
+        let msg = format!(
+            "El usuario {} creó la solicitud #{}, se requieren {} aprobaciones.",
+            &id_cuenta, id, tanda.aprobaciones
+        );
+        log_generator::create_log("ok", "solicitar_accion", &clave, &msg);
+
+        self.evaluar_solicitud(clave, id);
+        id
+    }
+
+    pub fn aprobar_accion(&mut self, clave: String, id: u32) -> String {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.es_administrador(&id_cuenta),
+            "El usuario {} no es administrador de esta Tanda.",
+            &id_cuenta
+        );
+
+        let mut solicitudes = self.solicitudes.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < solicitudes.len(),
+            "La solicitud #{} no existe.",
+            id
+        );
+
+        let solicitud = &mut solicitudes[id as usize];
+        assert!(
+            solicitud.estado == "Pendiente",
+            "La solicitud #{} ya fue cerrada.",
+            id
+        );
+        assert!(
+            solicitud.aprobaciones.insert(String::from(&id_cuenta)),
+            "El usuario {} ya aprobó la solicitud #{}.",
+            &id_cuenta,
+            id
+        );
+        self.solicitudes.insert(&clave, &solicitudes);
+
+        self.evaluar_solicitud(clave, id)
+    }
+
+    pub fn cancelar_solicitud(&mut self, clave: String, id: u32) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        let mut solicitudes = self.solicitudes.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < solicitudes.len(),
+            "La solicitud #{} no existe.",
+            id
+        );
+
+        let solicitud = &mut solicitudes[id as usize];
+        assert!(
+            solicitud.estado == "Pendiente",
+            "La solicitud #{} ya fue cerrada.",
+            id
+        );
+        assert!(
+            solicitud.solicitante == id_cuenta || tanda.es_administrador(&id_cuenta),
+            "No cuentas con autorización para cancelar la solicitud #{}.",
+            id
+        );
+
+        solicitud.estado = String::from("Cancelada");
+        self.solicitudes.insert(&clave, &solicitudes);
+    }
+
+    pub fn consultar_solicitudes(&self, clave: String) -> Vec<SolicitudAdministrativa> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.solicitudes.get(&clave).unwrap_or_default()
+    }
+
+    // ! MÉTODO INTERNO
+    fn evaluar_solicitud(&mut self, clave: String, id: u32) -> String {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut solicitudes = self.solicitudes.get(&clave).unwrap();
+        let solicitud = &mut solicitudes[id as usize];
+
+        // * Sólo cuentan las aprobaciones de quienes siguen siendo administradores
+        let aprobaciones = solicitud
+            .aprobaciones
+            .iter()
+            .filter(|cuenta| tanda.es_administrador(cuenta))
+            .count() as u32;
+
+        if aprobaciones < tanda.aprobaciones {
+            return String::from(&solicitud.estado);
+        }
+
+        solicitud.estado = String::from("Ejecutada");
+        let accion = solicitud.accion.clone();
+        self.solicitudes.insert(&clave, &solicitudes);
+
+        self.ejecutar_accion(clave, accion);
+        String::from("Ejecutada")
+    }
+
+    // ! MÉTODO INTERNO
+    fn ejecutar_accion(&mut self, clave: String, accion: AccionAdministrativa) {
+        match accion {
+            AccionAdministrativa::Activar => {
+                self.activar(clave);
+            }
+            AccionAdministrativa::RemoverIntegrante { cuenta } => {
+                self.quitar_integrante_pendiente(clave, cuenta);
+            }
+            AccionAdministrativa::Cancelar => {
+                self.cancelar(clave);
+            }
+            AccionAdministrativa::RegenerarPeriodos => {
+                self.regenerar(clave);
+            }
+            AccionAdministrativa::Editar {
+                nombre,
+                num_integrantes,
+                monto,
+                periodo,
+                fecha_inicio,
+            } => {
+                self.editar(clave, nombre, num_integrantes, monto, periodo, fecha_inicio);
+            }
+            AccionAdministrativa::Configurar { opciones } => {
+                self.validar_cambio_pool(&clave, &opciones);
+                let mut tanda = self.tandas.get(&clave).unwrap();
+                tanda.aplicar_opciones(opciones);
+                self.tandas.insert(&clave, &tanda);
+            }
+            AccionAdministrativa::AgregarOrganizador { cuenta, rol } => {
+                let mut tanda = self.tandas.get(&clave).unwrap();
+                assert!(
+                    cuenta != tanda.creador,
+                    "El creador ya es administrador de esta Tanda."
+                );
+                assert!(
+                    tanda.arbitro.as_ref() != Some(&cuenta),
+                    "El usuario {} es el árbitro de esta Tanda.",
+                    &cuenta
+                );
+
+                tanda.organizadores.insert(String::from(&cuenta), rol);
+                self.tandas.insert(&clave, &tanda);
+
+                let msg = format!("El usuario {} fue agregado como organizador.", &cuenta);
+                log_generator::create_log("ok", "agregar_organizador", &clave, &msg);
+            }
+            AccionAdministrativa::QuitarOrganizador { cuenta } => {
+                let mut tanda = self.tandas.get(&clave).unwrap();
+                assert!(
+                    tanda.organizadores.remove(&cuenta).is_some(),
+                    "El usuario {} no es organizador de esta Tanda.",
+                    &cuenta
+                );
+                assert!(
+                    tanda.aprobaciones <= tanda.num_administradores(),
+                    "No quedarían suficientes administradores para las {} aprobaciones requeridas.",
+                    tanda.aprobaciones
+                );
+                self.tandas.insert(&clave, &tanda);
+
+                let msg = format!("El usuario {} dejó de ser organizador.", &cuenta);
+                log_generator::create_log("ok", "quitar_organizador", &clave, &msg);
+            }
+        }
+    }
+
+    pub fn validar_pago_tanda(&mut self, clave: String, indice: i32) -> bool {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos para esta tanda no están inicializados."
+        );
+
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        let i = indice as usize;
+        let cantidad_a_pagar = tanda.cuota_periodo(&periodos[i]);
+
+        if periodos[i].cantidad_recaudada.0 >= cantidad_a_pagar
+            && tanda
+                .integrantes
+                .iter()
+                .all(|integrante| periodos[i].integrantes_pagados.contains(integrante))
+        {
+            periodos[i].pagos_completos = true;
+            self.periodos_tanda.insert(&clave, &periodos);
+
+            if tanda.asignacion_turnos == AsignacionTurnos::Sorteo
+                && !tanda.es_caja_ahorro()
+                && periodos[i].asiento_en_turno.is_none()
+            {
+                self.sortear_turno(clave, i);
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn escoger_turno(&mut self, clave: String, num_turno: usize, num_asiento: Option<u32>) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        assert!(
+            !self.tandas.get(&clave).unwrap().es_caja_ahorro(),
+            "Las cajas de ahorro no tienen turnos."
+        );
+        assert!(
+            self.tandas.get(&clave).unwrap().asignacion_turnos != AsignacionTurnos::Sorteo,
+            "En esta Tanda los turnos se sortean al completar cada periodo."
+        );
+        let id_cuenta = env::predecessor_account_id();
+        let valido = self.validar_integrante(String::from(&clave), String::from(&id_cuenta));
+        assert!(
+            valido,
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos no están inicializados"
+        );
+
+        self.cobrar_monedero(String::from(&clave), String::from(&id_cuenta));
+
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+
+        assert!(
+            num_turno <= periodos.len() && num_turno > 0,
+            "La tanda sólo contiene {} espacios.",
+            periodos.len()
+        );
+
+        assert!(
+            periodos[num_turno - 1].usuario_en_turno == String::new(),
+            "El turno {} ya está tomado por {}",
+            num_turno,
+            periodos[num_turno - 1].usuario_en_turno
+        );
+
+        // * Cada asiento de la cuenta puede reservar un solo turno
+        let tanda = self.tandas.get(&clave).unwrap();
+        let asientos_sin_turno: Vec<u32> = tanda
+            .asientos
+            .iter()
+            .filter(|asiento| asiento.participaciones.contains_key(&id_cuenta))
+            .map(|asiento| asiento.numero)
+            .filter(|numero| {
+                !periodos
+                    .iter()
+                    .any(|periodo| periodo.asiento_en_turno == Some(*numero))
+            })
+            .collect();
+
+        let asiento = match num_asiento {
+            Some(numero) => {
+                assert!(
+                    asientos_sin_turno.contains(&numero),
+                    "El asiento {} no pertenece a {} o ya tiene un turno asignado.",
+                    numero,
+                    &id_cuenta
+                );
+                numero
+            }
+            None => {
+                assert!(
+                    !asientos_sin_turno.is_empty(),
+                    "Todos los asientos de {} ya tienen un turno asignado.",
+                    &id_cuenta
+                );
+                asientos_sin_turno[0]
+            }
+        };
+
+        periodos[num_turno - 1].usuario_en_turno = env::predecessor_account_id();
+        periodos[num_turno - 1].asiento_en_turno = Some(asiento);
+
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        let msg = format!(
+            "El usuario {} ha tomado exitosamente el turno {} en la Tanda.",
+            env::predecessor_account_id(),
+            num_turno
+        );
+        env::log(msg.as_bytes());
+    }
+
+    // ! MÉTODO INTERNO
+    fn sortear_turno(&mut self, clave: String, indice: usize) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+
+        // * Sólo participan los asientos que aún no han tenido turno en el ciclo
+        let candidatos: Vec<u32> = tanda
+            .asientos
+            .iter()
+            .map(|asiento| asiento.numero)
+            .filter(|numero| {
+                !periodos
+                    .iter()
+                    .any(|periodo| periodo.asiento_en_turno == Some(*numero))
+            })
+            .collect();
+
+        if candidatos.is_empty() {
+            env::log("No quedan asientos sin turno para sortear.".as_bytes());
+            return;
+        }
+
+        let numero = sorteo::numero_aleatorio(indice as u64);
+        let ganador = candidatos[(numero % candidatos.len() as u64) as usize];
+
+        let mut titulares: Vec<&AccountId> = tanda.asientos[ganador as usize - 1]
+            .participaciones
+            .keys()
+            .collect();
+        titulares.sort();
+
+        periodos[indice].asiento_en_turno = Some(ganador);
+        periodos[indice].usuario_en_turno = String::from(titulares[0]);
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        let msg = format!(
+            "Sorteo del periodo #{}: semilla {}, bloque {}, ronda {}, número {}, candidatos {:?}. Ganó el asiento {} de {}.",
+            indice + 1,
+            sorteo::semilla_hex(),
+            env::block_index(),
+            indice,
+            numero,
+            candidatos,
+            ganador,
+            titulares[0]
+        );
+        log_generator::create_log("ok", "sortear_turno", &clave, &msg);
+    }
+
+    pub fn validar_periodo(&self, clave: String, id_cuenta: Option<String>) -> i32 {
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos para esta tanda no están inicializados."
+        );
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+
+        for (n, periodo) in periodos.iter().enumerate() {
+            if !periodo.integrantes_pagados.contains(&cuenta) {
+                return n as i32;
+            }
+        }
+
+        -1
+    }
+
+    pub fn generar_periodos(&mut self, clave: String) {
+        if let Some(tanda) = self.tandas.get(&clave) {
+            match self.periodos_tanda.get(&clave) {
+                Some(_periodos) => {
+                    // generar log de que ya estaban inicializados..
+                }
+                None => {
+                    //Checar errores...
+                    let mut vec_periodos = Vec::<Periodo>::new();
+                    let mut fecha_inicio = String::from(&tanda.fecha_inicio);
+
+                    for _n in 0..tanda.num_periodos() {
+                        let fecha_final =
+                            date_handling::agregar_dias(&fecha_inicio, (tanda.periodo - 1) as i64);
+                        let periodo: Periodo = Periodo::new(
+                            String::from(&fecha_inicio),
+                            String::from(&fecha_final),
+                            tanda.monto,
+                        );
+
+                        vec_periodos.push(periodo);
+
+                        fecha_inicio = date_handling::agregar_dias(&fecha_final, 1);
+                    }
+
+                    self.periodos_tanda.insert(&clave, &vec_periodos);
+                }
+            }
+        }
+    }
+
+    pub fn consultar_periodos(&self, clave: String) -> Option<Vec<Periodo>> {
+        self.periodos_tanda.get(&clave)
+    }
+
+    pub fn obtener_periodo_a_pagar(&self, clave: String) -> i32 {
+        assert!(
+            clave != String::new(),
+            "El campo clave no debe estar vacío."
+        );
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe");
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos para esta tanda no están inicializados."
+        );
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+
+        for (n, periodo) in periodos.iter().enumerate() {
+            if !periodo.tanda_pagada {
+                return n as i32;
+            }
+        }
+        -1
+    }
+
+    pub fn pagar_tanda(&mut self, clave: String, indice: i32) -> bool {
+        assert!(
+            clave != String::new(),
+            "El campo clave no debe estar vacío."
+        );
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe");
+        assert!(
+            self.periodos_tanda.get(&clave).is_some(),
+            "Los periodos para esta tanda no están inicializados."
+        );
+        assert!(
+            !self.tandas.get(&clave).unwrap().pausada,
+            "La Tanda se encuentra pausada."
+        );
+        assert!(
+            !self.tandas.get(&clave).unwrap().es_caja_ahorro(),
+            "Las cajas de ahorro se pagan al vencimiento con liquidar_caja_ahorro."
+        );
+        assert!(
+            !self.tanda_congelada(&clave),
+            "Los pagos de esta Tanda están congelados por una disputa abierta."
+        );
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+        let n = indice as usize;
+
+        assert!(
+            self.fondos_disponibles(&clave, n),
+            "Los fondos de este periodo están delegados al pool de staking, deben retirarse antes de pagar."
+        );
+
+        assert!(
+            periodos[n].pagos_completos,
+            "Este periodo aún no puede ser pagado."
+        );
+
+        env::log("Validando que este periodo pueda ser pagado...".as_bytes());
+        assert!(
+            periodos[n].usuario_en_turno != String::new(),
+            "No hay usuario en turno en este periodo."
+        );
+
+        assert!(!periodos[n].tanda_pagada, "Este periodo ya fue pagado.");
+
+        self.acreditar_periodo(clave, n);
+
+        true
+    }
+
+    // ! MÉTODO INTERNO
+    fn acreditar_periodo(&mut self, clave: String, indice: usize) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        let monto = periodos[indice].cantidad_recaudada.0;
+
+        // * El pago se reparte entre los titulares del asiento en turno
+        let mut titulares: Vec<(AccountId, u32)> = match periodos[indice].asiento_en_turno {
+            Some(numero) => tanda.asientos[numero as usize - 1]
+                .participaciones
+                .iter()
+                .map(|(cuenta, participacion)| (String::from(cuenta), *participacion))
+                .collect(),
+            None => vec![(
+                String::from(&periodos[indice].usuario_en_turno),
+                PARTICIPACION_COMPLETA,
+            )],
+        };
+        titulares.sort();
+
+        let mut restante = monto;
+        for (n, (titular, participacion)) in titulares.iter().enumerate() {
+            let parte = if n == titulares.len() - 1 {
+                restante
+            } else {
+                prorrata(
+                    monto,
+                    *participacion as u128,
+                    PARTICIPACION_COMPLETA as u128,
+                )
+            };
+            restante -= parte;
+
+            periodos[indice]
+                .beneficiarios
+                .insert(String::from(titular), U128(parte));
+        }
+
+        periodos[indice].tanda_pagada = true;
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        if !periodos[indice].pagos_externos.is_empty() {
+            let externo: u128 = periodos[indice]
+                .pagos_externos
+                .iter()
+                .map(|cuenta| tanda.cuota_monto(cuenta, periodos[indice].monto))
+                .sum();
+            let msg = format!(
+                "{} yoctoNEAR del periodo #{} se pagaron en efectivo fuera del contrato.",
+                externo,
+                indice + 1
+            );
+            env::log(msg.as_bytes());
+        }
+
+        for (beneficiario, parte) in periodos[indice].beneficiarios.iter() {
+            let descuento = self.descontar_prestamo(&clave, beneficiario, parte.0);
+            self.acreditar_saldo(
+                beneficiario,
+                parte.0 - descuento,
+                ConceptoMovimiento::PagoTanda,
+                format!("{}#{}", clave, indice + 1),
+            );
+
+            let msg = format!(
+                "La Tanda fue pagada exitosamente. El usuario {} recibió {} yoctoNEAR correspondientes al periodo #{}.",
+                beneficiario,
+                parte.0,
+                indice + 1
+            );
+            env::log(msg.as_bytes());
+        }
+
+        if periodos.iter().all(|periodo| periodo.tanda_pagada) {
+            self.cerrar_ciclo(clave);
+        }
+    }
+
+    // ! MÉTODO INTERNO
+    fn cerrar_ciclo(&mut self, clave: String) {
+        let mut tanda = self.tandas.get(&clave).unwrap();
+
+        tanda.activa = false;
+        tanda.estado = "Finalizada".to_string();
+
+        let msg = format!("La Tanda {} finalizó su ciclo #{}.", &tanda.id, tanda.ciclo);
+        env::log(msg.as_bytes());
+
+        self.tandas.insert(&clave, &tanda);
+        self.repartir_rendimiento(String::from(&clave));
+        self.repartir_intereses(String::from(&clave));
+        self.repartir_reserva(String::from(&clave));
+
+        if tanda.renovable {
+            tanda.ciclo_siguiente = self.renovar_tanda(&tanda);
+        }
+
+        self.tandas.insert(&clave, &tanda);
+    }
+
+    // ! MÉTODO INTERNO
+    fn renovar_tanda(&mut self, tanda: &Tanda) -> Option<String> {
+        let periodos = self.periodos_tanda.get(&tanda.id).unwrap();
+
+        // * Los asientos se ordenan según el turno que tuvieron en el ciclo anterior
+        let mut orden: Vec<u32> = periodos
+            .iter()
+            .filter_map(|periodo| periodo.asiento_en_turno)
+            .collect();
+        for asiento in tanda.asientos.iter() {
+            if !orden.contains(&asiento.numero) {
+                orden.push(asiento.numero);
+            }
+        }
+
+        let mut asientos: Vec<Asiento> = orden
+            .iter()
+            .map(|numero| tanda.asientos[*numero as usize - 1].clone())
+            .filter(|asiento| {
+                asiento
+                    .participaciones
+                    .keys()
+                    .all(|cuenta| tanda.renovaciones.contains(cuenta))
+            })
+            .collect();
+
+        // * Quien confirmó pasa por las mismas validaciones que al unirse, el ciclo que termina ya no cuenta
+        let mut rechazados = HashSet::<AccountId>::new();
+        for asiento in asientos.iter() {
+            for cuenta in asiento.participaciones.keys() {
+                let motivo = self
+                    .impedimento_ingreso(tanda, cuenta)
+                    .or_else(|| self.exceso_exposicion(tanda, cuenta));
+                if let Some(motivo) = motivo {
+                    log_generator::create_log("error", "renovar_tanda", &tanda.id, &motivo);
+                    rechazados.insert(String::from(cuenta));
+                }
+            }
+        }
+        asientos.retain(|asiento| {
+            asiento
+                .participaciones
+                .keys()
+                .all(|cuenta| !rechazados.contains(cuenta))
+        });
+
+        if asientos.len() < 2 {
+            env::log(
+                "No hay suficientes integrantes confirmados para renovar la Tanda.".as_bytes(),
+            );
+            return None;
+        }
+
+        match tanda.orden_renovacion {
+            OrdenTurnos::Rotar => asientos.rotate_left(1),
+            OrdenTurnos::Sortear => sorteo::mezclar(&mut asientos),
+        }
+
+        // * Creación del nuevo ciclo
+        let mut nueva = Tanda::new(
+            String::from(&tanda.nombre_tanda),
+            asientos.len() as u32,
+            tanda.monto,
+            tanda.periodo,
+            OpcionesTanda {
+                aprobaciones: None,
+                ..tanda.opciones()
+            },
+        );
+        nueva.organizadores = tanda.organizadores.clone();
+        nueva.aprobaciones = tanda.aprobaciones;
+        nueva.arbitro = tanda.arbitro.clone();
+        nueva.ahorro = tanda.ahorro.as_ref().map(|caja| caja.reiniciar());
+        nueva.actualizar_fecha_final();
+        nueva.id = format!(
+            "{}-{}",
+            tanda.id.split('-').next().unwrap(),
+            tanda.ciclo + 1
+        );
+        nueva.creador = String::from(&tanda.creador);
+        nueva.ciclo = tanda.ciclo + 1;
+        nueva.ciclo_anterior = Some(String::from(&tanda.id));
+
+        for (n, asiento) in asientos.iter_mut().enumerate() {
+            asiento.numero = n as u32 + 1;
+            for cuenta in asiento.participaciones.keys() {
+                nueva.integrantes.insert(String::from(cuenta));
+            }
+        }
+        nueva.asientos = asientos;
+
+        self.tandas.insert(&nueva.id, &nueva);
+        self.generar_periodos(String::from(&nueva.id));
+
+        // * Los turnos quedan asignados según el nuevo orden
+        let mut nuevos_periodos = self.periodos_tanda.get(&nueva.id).unwrap();
+        let asientos_en_turno =
+            if nueva.es_caja_ahorro() || nueva.asignacion_turnos == AsignacionTurnos::Sorteo {
+                0
+            } else {
+                nueva.asientos.len()
+            };
+        for (periodo, asiento) in nuevos_periodos
+            .iter_mut()
+            .zip(nueva.asientos.iter().take(asientos_en_turno))
+        {
+            let mut titulares: Vec<&AccountId> = asiento.participaciones.keys().collect();
+            titulares.sort();
+
+            periodo.asiento_en_turno = Some(asiento.numero);
+            periodo.usuario_en_turno = String::from(titulares[0]);
+        }
+        self.periodos_tanda.insert(&nueva.id, &nuevos_periodos);
+
+        // * Registro de usuarios y traspaso de monederos
+        self.registrar_usuario(String::from(&nueva.creador), String::from(&nueva.id), true);
+
+        let monederos = self.monederos.get(&tanda.id).unwrap_or_default();
+        let mut restantes = HashMap::<AccountId, u128>::new();
+        let mut traspasados = HashMap::<AccountId, u128>::new();
+
+        for (cuenta, saldo) in monederos.into_iter() {
+            if nueva.integrantes.contains(&cuenta) {
+                traspasados.insert(cuenta, saldo);
+            } else {
+                restantes.insert(cuenta, saldo);
+            }
+        }
+        self.monederos.insert(&tanda.id, &restantes);
+        self.monederos.insert(&nueva.id, &traspasados);
+
+        for integrante in nueva.integrantes.iter() {
+            self.registrar_usuario(String::from(integrante), String::from(&nueva.id), false);
+        }
+
+        let msg = format!(
+            "La Tanda {} se renovó como {} (ciclo #{}) con {} integrantes, queda pendiente de activar.",
+            &tanda.id,
+            &nueva.id,
+            nueva.ciclo,
+            nueva.integrantes.len()
+        );
+        env::log(msg.as_bytes());
+
+        Some(nueva.id)
+    }
+
+    pub fn confirmar_renovacion(&mut self, clave: String, renovar: bool) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.integrantes.contains(&id_cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+        assert!(tanda.renovable, "Esta Tanda no se renueva automáticamente.");
+        assert!(
+            tanda.ciclo_siguiente.is_none(),
+            "Esta Tanda ya fue renovada."
+        );
+
+        if renovar {
+            tanda.renovaciones.insert(String::from(&id_cuenta));
+        } else {
+            tanda.renovaciones.remove(&id_cuenta);
+        }
+        self.tandas.insert(&clave, &tanda);
+
+        let msg = format!(
+            "El usuario {} {} en el siguiente ciclo de la Tanda {}.",
+            &id_cuenta,
+            if renovar {
+                "participará"
+            } else {
+                "no participará"
+            },
+            &clave
+        );
+        env::log(msg.as_bytes());
+    }
+
+    pub fn consultar_ciclos(&self, clave: String) -> Vec<Tanda> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+
+        let mut primera = self.tandas.get(&clave).unwrap();
+        while let Some(anterior) = &primera.ciclo_anterior {
+            primera = self.tandas.get(anterior).unwrap();
+        }
+
+        let mut ciclos = vec![primera];
+        while let Some(siguiente) = &ciclos[ciclos.len() - 1].ciclo_siguiente {
+            let tanda = self.tandas.get(siguiente).unwrap();
+            ciclos.push(tanda);
+        }
+
+        ciclos
+    }
+
+    // ! MÉTODO INTERNO
+    fn acreditar_saldo(
+        &mut self,
+        cuenta: &AccountId,
+        monto: u128,
+        concepto: ConceptoMovimiento,
+        referencia: String,
+    ) {
+        let saldo = self.saldos.get(cuenta).unwrap_or(0) + monto;
+        self.saldos.insert(cuenta, &saldo);
+
+        self.registrar_movimiento(MovimientoSaldo::new(
+            String::from(cuenta),
+            concepto,
+            true,
+            monto,
+            saldo,
+            referencia,
+        ));
+    }
+
+    // ! MÉTODO INTERNO
+    fn debitar_saldo(
+        &mut self,
+        cuenta: &AccountId,
+        monto: u128,
+        concepto: ConceptoMovimiento,
+        referencia: String,
+    ) {
+        let saldo = self.saldos.get(cuenta).unwrap_or(0);
+        assert!(
+            monto <= saldo,
+            "Saldo insuficiente, sólo hay {} yoctoNEAR disponibles.",
+            saldo
+        );
+
+        if saldo == monto {
+            self.saldos.remove(cuenta);
+        } else {
+            self.saldos.insert(cuenta, &(saldo - monto));
+        }
+
+        self.registrar_movimiento(MovimientoSaldo::new(
+            String::from(cuenta),
+            concepto,
+            false,
+            monto,
+            saldo - monto,
+            referencia,
+        ));
+    }
+
+    // ! MÉTODO INTERNO
+    fn registrar_movimiento(&mut self, movimiento: MovimientoSaldo) {
+        let mut indices = self
+            .movimientos_cuenta
+            .get(&movimiento.cuenta)
+            .unwrap_or_default();
+        indices.push(self.movimientos_saldo.len());
+        self.movimientos_cuenta.insert(&movimiento.cuenta, &indices);
+
+        self.movimientos_saldo.push(&movimiento);
+    }
+
+    pub fn retirar(&mut self, monto: Option<U128>) -> Promise {
+        let id_cuenta = env::predecessor_account_id();
+        let saldo = self.saldos.get(&id_cuenta).unwrap_or(0);
+        let monto_retiro = monto.map(|m| m.0).unwrap_or(saldo);
+
+        assert!(monto_retiro > 0, "No tienes saldo disponible para retirar.");
+
+        self.debitar_saldo(
+            &id_cuenta,
+            monto_retiro,
+            ConceptoMovimiento::Retiro,
+            String::new(),
+        );
+
+        let msg = format!(
+            "El usuario {} retiró {} yoctoNEAR de su saldo.",
+            id_cuenta, monto_retiro
+        );
+        env::log(msg.as_bytes());
+
+        Promise::new(String::from(&id_cuenta))
+            .transfer(monto_retiro)
+            .then(ext_self::resolver_retiro(
+                id_cuenta,
+                U128(monto_retiro),
+                &env::current_account_id(),
+                0,
+                GAS_CALLBACK,
+            ))
+    }
+
+    #[private]
+    pub fn resolver_retiro(&mut self, cuenta: AccountId, monto: U128) -> bool {
+        assert_eq!(
+            env::promise_results_count(),
+            1,
+            "Se esperaba el resultado de una transferencia."
+        );
+
+        match env::promise_result(0) {
+            PromiseResult::Successful(_) => true,
+            _ => {
+                self.acreditar_saldo(
+                    &cuenta,
+                    monto.0,
+                    ConceptoMovimiento::RetiroFallido,
+                    String::new(),
+                );
+
+                let msg = format!(
+                    "El retiro de {} yoctoNEAR para {} falló, el monto se devolvió a su saldo.",
+                    monto.0, cuenta
+                );
+                env::log(msg.as_bytes());
+
+                false
+            }
+        }
+    }
+
+    pub fn consultar_saldo(&self, id_cuenta: Option<String>) -> U128 {
+        U128(
+            self.saldos
+                .get(&id_cuenta.unwrap_or(env::predecessor_account_id()))
+                .unwrap_or(0),
+        )
+    }
+
+    // Sin id_cuenta devuelve el diario completo de movimientos.
+    pub fn consultar_movimientos(
+        &self,
+        id_cuenta: Option<String>,
+        desde: Option<u64>,
+        limite: Option<u64>,
+    ) -> Vec<MovimientoSaldo> {
+        let desde = desde.unwrap_or(0);
+        let limite = cmp::min(limite.unwrap_or(MAX_PAGE_SIZE), MAX_PAGE_SIZE);
+
+        // * Con cuenta se recorre sólo su índice de movimientos
+        let indices: Vec<u64> = match id_cuenta {
+            Some(cuenta) => self
+                .movimientos_cuenta
+                .get(&cuenta)
+                .unwrap_or_default()
+                .into_iter()
+                .skip(desde as usize)
+                .take(limite as usize)
+                .collect(),
+            None => (desde..cmp::min(desde.saturating_add(limite), self.movimientos_saldo.len()))
+                .collect(),
+        };
+
+        indices
+            .iter()
+            .map(|indice| self.movimientos_saldo.get(*indice).unwrap())
+            .collect()
+    }
+
+    // ! MÉTODO INTERNO
+    fn integrante_en_mora(&self, clave: &String, cuenta: &AccountId) -> bool {
+        let prestamo_vencido = self.prestamos.get(clave).is_some_and(|cartera| {
+            cartera
+                .prestamos
+                .iter()
+                .any(|prestamo| &prestamo.deudor == cuenta && prestamo.vencido())
+        });
+        if prestamo_vencido {
+            return true;
+        }
+
+        let adelantos_pendientes = self
+            .reservas
+            .get(clave)
+            .is_some_and(|fondo| fondo.pendiente(cuenta) > 0);
+        if adelantos_pendientes {
+            return true;
+        }
+
+        match self.periodos_tanda.get(clave) {
+            Some(periodos) => periodos.iter().any(|periodo| {
+                !periodo.integrantes_pagados.contains(cuenta)
+                    && date_handling::periodo_vencido(&periodo.fin)
+            }),
+            None => false,
+        }
+    }
+
+    pub fn proponer(&mut self, clave: String, tipo: TipoPropuesta) -> u32 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.integrantes.contains(&id_cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+        assert!(
+            tanda.activa
+                || (tanda.estado == "Pendiente"
+                    && matches!(tipo, TipoPropuesta::ReemplazarCreador { .. })),
+            "Sólo se pueden hacer propuestas en Tandas activas."
+        );
+
+        // * Validación según el tipo de propuesta
+        match &tipo {
+            TipoPropuesta::CambiarMonto { monto } => {
+                assert!(*monto > 0, "El monto a ahorrar tiene que ser mayor a 0.")
+            }
+            TipoPropuesta::CambiarPeriodo { periodo } => {
+                assert!(*periodo > 0, "El periodo no puede ser menor a 1.")
+            }
+            TipoPropuesta::Expulsar { cuenta } => {
+                assert!(
+                    tanda.integrantes.contains(cuenta),
+                    "El usuario {} no es integrante de esta tanda.",
+                    cuenta
+                );
+                assert!(
+                    cuenta != &id_cuenta,
+                    "El usuario {} no puede proponer su propia expulsión.",
+                    &id_cuenta
+                );
+                assert!(
+                    self.integrante_en_mora(&clave, cuenta),
+                    "El usuario {} está al corriente con sus pagos.",
+                    cuenta
+                );
+            }
+            TipoPropuesta::Pausar => assert!(!tanda.pausada, "La Tanda ya está pausada."),
+            TipoPropuesta::Reanudar => assert!(tanda.pausada, "La Tanda no está pausada."),
+            TipoPropuesta::Disolver => assert!(
+                self.staking
+                    .get(&clave)
+                    .map_or(0, |staking| staking.inmovilizado())
+                    == 0,
+                "Primero deben retirarse los fondos delegados al pool de staking."
+            ),
+            TipoPropuesta::ReemplazarCreador { cuenta } => {
+                assert!(
+                    cuenta != &tanda.creador,
+                    "El usuario {} ya es el creador de esta Tanda.",
+                    cuenta
+                );
+                assert!(
+                    tanda.arbitro.as_ref() != Some(cuenta),
+                    "El usuario {} es el árbitro de esta Tanda.",
+                    cuenta
+                );
+            }
+        }
+
+        let mut propuestas = self.propuestas.get(&clave).unwrap_or_default();
+        let id = propuestas.len() as u32;
+        let mut propuesta = Propuesta::new(id, tipo, tanda.quorum, tanda.umbral);
+
+        propuesta.votos_favor.insert(String::from(&id_cuenta));
+        propuestas.push(propuesta);
+        self.propuestas.insert(&clave, &propuestas);
+
+        let msg = format!(
+            "El usuario {} creó la propuesta #{} en la Tanda {}.",
+            &id_cuenta, id, &clave
+        );
+        env::log(msg.as_bytes());
+
+        self.evaluar_propuesta(clave, id);
+        id
+    }
+
+    pub fn votar(&mut self, clave: String, id: u32, a_favor: bool) -> String {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.integrantes.contains(&id_cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+
+        let mut propuestas = self.propuestas.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < propuestas.len(),
+            "La propuesta #{} no existe.",
+            id
+        );
+
+        let propuesta = &mut propuestas[id as usize];
+        assert!(
+            propuesta.estado == "Abierta",
+            "La propuesta #{} ya fue cerrada.",
+            id
+        );
+        assert!(
+            !propuesta.vencida(),
+            "La votación de la propuesta #{} terminó el {}.",
+            id,
+            &propuesta.cierre
+        );
+        if let TipoPropuesta::Expulsar { cuenta } = &propuesta.tipo {
+            assert!(
+                cuenta != &id_cuenta,
+                "El usuario {} no puede votar en su propia expulsión.",
+                &id_cuenta
+            );
+        }
+        assert!(
+            !propuesta.votos_favor.contains(&id_cuenta)
+                && !propuesta.votos_contra.contains(&id_cuenta),
+            "El usuario {} ya votó en la propuesta #{}.",
+            &id_cuenta,
+            id
+        );
+
+        if a_favor {
+            propuesta.votos_favor.insert(String::from(&id_cuenta));
+        } else {
+            propuesta.votos_contra.insert(String::from(&id_cuenta));
+        }
+        self.propuestas.insert(&clave, &propuestas);
+
+        self.evaluar_propuesta(clave, id)
+    }
+
+    // Decide la propuesta con los votos emitidos una vez que terminó su votación.
+    pub fn cerrar_propuesta(&mut self, clave: String, id: u32) -> String {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let propuestas = self.propuestas.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < propuestas.len(),
+            "La propuesta #{} no existe.",
+            id
+        );
+
+        let propuesta = &propuestas[id as usize];
+        assert!(
+            propuesta.estado == "Abierta",
+            "La propuesta #{} ya fue cerrada.",
+            id
+        );
+        assert!(
+            propuesta.vencida(),
+            "La votación de la propuesta #{} termina el {}.",
+            id,
+            &propuesta.cierre
+        );
+
+        self.evaluar_propuesta(clave, id)
+    }
+
+    // ! MÉTODO INTERNO
+    fn evaluar_propuesta(&mut self, clave: String, id: u32) -> String {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut propuestas = self.propuestas.get(&clave).unwrap();
+        let propuesta = &mut propuestas[id as usize];
+        let votantes = match &propuesta.tipo {
+            TipoPropuesta::Expulsar { .. } => tanda.integrantes.len() as u32 - 1,
+            _ => tanda.integrantes.len() as u32,
+        };
+
+        if propuesta.aprobada(votantes) {
+            propuesta.estado = String::from("Aprobada");
+        } else if propuesta.rechazada(votantes) {
+            propuesta.estado = String::from("Rechazada");
+        }
+
+        let estado = String::from(&propuesta.estado);
+        let tipo = propuesta.tipo.clone();
+        self.propuestas.insert(&clave, &propuestas);
+
+        let msg = format!(
+            "La propuesta #{} de la Tanda {} se encuentra {}.",
+            id, &clave, &estado
+        );
+        env::log(msg.as_bytes());
+
+        if estado == "Aprobada" {
+            self.ejecutar_propuesta(clave, tipo);
+        }
+
+        estado
+    }
+
+    // ! MÉTODO INTERNO
+    fn ejecutar_propuesta(&mut self, clave: String, tipo: TipoPropuesta) {
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+
+        match tipo {
+            // * Los cambios sólo aplican a periodos sin aportaciones
+            TipoPropuesta::CambiarMonto { monto } => {
+                tanda.monto = monto;
+                for periodo in periodos.iter_mut() {
+                    if periodo.integrantes_pagados.is_empty() && periodo.abonos_parciales.is_empty()
+                    {
+                        periodo.monto = monto;
+                    }
+                }
+            }
+            TipoPropuesta::CambiarPeriodo { periodo: dias } => {
+                tanda.periodo = dias;
+
+                let primero = periodos
+                    .iter()
+                    .position(|periodo| {
+                        periodo.integrantes_pagados.is_empty()
+                            && periodo.abonos_parciales.is_empty()
+                            && !date_handling::fecha_alcanzada(&periodo.inicio)
+                    })
+                    .unwrap_or(periodos.len());
+
+                for n in primero..periodos.len() {
+                    if n > 0 {
+                        periodos[n].inicio = date_handling::agregar_dias(&periodos[n - 1].fin, 1);
+                    }
+                    periodos[n].fin =
+                        date_handling::agregar_dias(&periodos[n].inicio, (dias - 1) as i64);
+                }
+
+                if let Some(ultimo) = periodos.last() {
+                    tanda.fecha_final = String::from(&ultimo.fin);
+                }
+            }
+            TipoPropuesta::Expulsar { cuenta } => {
+                self.expulsar_integrante(String::from(&clave), cuenta);
+                return;
+            }
+            TipoPropuesta::Pausar => {
+                tanda.pausada = true;
+                tanda.estado = "Pausada".to_string();
+            }
+            TipoPropuesta::Reanudar => {
+                tanda.pausada = false;
+                tanda.estado = "Activa".to_string();
+            }
+            TipoPropuesta::Disolver => {
+                self.disolver_tanda(clave);
+                return;
+            }
+            // * Recuperación de una Tanda cuyo creador perdió el acceso
+            TipoPropuesta::ReemplazarCreador { cuenta } => {
+                let anterior = std::mem::replace(&mut tanda.creador, String::from(&cuenta));
+                tanda.organizadores.remove(&cuenta);
+                tanda.aprobaciones = cmp::min(tanda.aprobaciones, tanda.num_administradores());
+
+                if let Some(mut usuario) = self.usuarios.get(&anterior) {
+                    usuario.tandas_creadas.retain(|id| id != &clave);
+                    self.usuarios.insert(&anterior, &usuario);
+                }
+                self.registrar_usuario(String::from(&cuenta), String::from(&clave), true);
+
+                let msg = format!(
+                    "El usuario {} reemplazó a {} como creador de la Tanda.",
+                    &cuenta, &anterior
+                );
+                log_generator::create_log("ok", "reemplazar_creador", &clave, &msg);
+            }
+        }
+
+        self.tandas.insert(&clave, &tanda);
+        self.periodos_tanda.insert(&clave, &periodos);
+    }
+
+    // ! MÉTODO INTERNO
+    fn expulsar_integrante(&mut self, clave: String, cuenta: AccountId) {
+        // * En una caja de ahorro la expulsión equivale a un retiro anticipado
+        if self.tandas.get(&clave).unwrap().es_caja_ahorro() {
+            self.retirar_de_caja(String::from(&clave), String::from(&cuenta));
+
+            let msg = format!(
+                "El usuario {} fue expulsado de la caja de ahorro {}.",
+                &cuenta, &clave
+            );
+            env::log(msg.as_bytes());
+            return;
+        }
+
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+
+        let mut vacios = tanda.quitar_integrante(&cuenta);
+        vacios.sort_unstable();
+
+        // * Cada asiento vacío libera un periodo que aún no ha sido pagado
+        for numero in vacios.iter().rev() {
+            let asignado = periodos
+                .iter()
+                .position(|periodo| periodo.asiento_en_turno == Some(*numero));
+            let liberado = match asignado {
+                Some(n) if !periodos[n].tanda_pagada => Some(n),
+                Some(_) => None,
+                None => periodos.iter().rposition(|periodo| {
+                    !periodo.tanda_pagada && periodo.asiento_en_turno.is_none()
+                }),
+            };
+
+            if let Some(n) = liberado {
+                let periodo = periodos.remove(n);
+                let referencia = format!("{}#{}", clave, n + 1);
+
+                // * Los periodos siguientes se recorren para no dejar un hueco en el calendario
+                let dias = date_handling::dias_entre(&periodo.inicio, &periodo.fin) + 1;
+                for siguiente in periodos[n..].iter_mut() {
+                    siguiente.inicio = date_handling::agregar_dias(&siguiente.inicio, -dias);
+                    siguiente.fin = date_handling::agregar_dias(&siguiente.fin, -dias);
+                }
+
+                let mut pagadores: HashSet<&AccountId> = periodo.aportaciones.keys().collect();
+                pagadores.extend(periodo.abonos_parciales.keys());
+
+                for pagador in pagadores {
+                    self.acreditar_saldo(
+                        pagador,
+                        periodo.aportado(pagador),
+                        ConceptoMovimiento::Reembolso,
+                        String::from(&referencia),
+                    );
+                }
+            }
+
+            tanda.eliminar_asiento(*numero);
+            for periodo in periodos.iter_mut() {
+                match periodo.asiento_en_turno {
+                    Some(asiento) if asiento > *numero => {
+                        periodo.asiento_en_turno = Some(asiento - 1)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(ultimo) = periodos.last() {
+            tanda.fecha_final = String::from(&ultimo.fin);
+        }
+
+        // * Si ya cobró más de lo que aportó, la diferencia queda como deuda con quienes no han cobrado
+        let aportado: u128 = periodos
+            .iter()
+            .map(|periodo| periodo.aportado(&cuenta))
+            .sum();
+        let recibido: u128 = periodos
+            .iter()
+            .filter_map(|periodo| periodo.beneficiarios.get(&cuenta))
+            .map(|recibido| recibido.0)
+            .sum();
+
+        // * Lo que aportó de más se le reembolsa de los periodos sin pagar, empezando por el último
+        let mut a_favor = aportado.saturating_sub(recibido);
+        let mut reembolsos = Vec::<(String, u128)>::new();
+        for (n, periodo) in periodos.iter_mut().enumerate().rev() {
+            if a_favor == 0 {
+                break;
+            }
+            if periodo.tanda_pagada || periodo.aportado(&cuenta) == 0 {
+                continue;
+            }
+
+            // * Los abonos parciales no cuentan en lo recaudado, se devuelven primero
+            let abonado = periodo.abonos_parciales.remove(&cuenta).map_or(0, |m| m.0);
+            let aportacion = periodo.aportaciones.remove(&cuenta).map_or(0, |m| m.0);
+            let monto = cmp::min(abonado + aportacion, a_favor);
+            let resta_abono = abonado.saturating_sub(monto);
+            let resta_aportacion = aportacion - (monto - (abonado - resta_abono));
+
+            if resta_abono > 0 {
+                periodo
+                    .abonos_parciales
+                    .insert(String::from(&cuenta), U128(resta_abono));
+            }
+            if resta_aportacion > 0 {
+                periodo
+                    .aportaciones
+                    .insert(String::from(&cuenta), U128(resta_aportacion));
+            } else {
+                periodo.integrantes_pagados.remove(&cuenta);
+            }
+            periodo.cantidad_recaudada =
+                U128(periodo.cantidad_recaudada.0 - (aportacion - resta_aportacion));
+
+            a_favor -= monto;
+            reembolsos.push((format!("{}#{}", clave, n + 1), monto));
+        }
+
+        self.tandas.insert(&clave, &tanda);
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        self.vaciar_monedero(&clave, &cuenta);
+
+        if let Some(mut usuario) = self.usuarios.get(&cuenta) {
+            usuario.tandas_inscritas.retain(|id| id != &clave);
+            self.usuarios.insert(&cuenta, &usuario);
+        }
+
+        for (referencia, monto) in reembolsos {
+            self.acreditar_saldo(&cuenta, monto, ConceptoMovimiento::Reembolso, referencia);
+        }
+        if recibido > aportado {
+            self.registrar_deuda_expulsion(&clave, &cuenta, recibido - aportado);
+        }
+        // * Lo que ya se pagó a otros turnos queda como deuda de quienes lo cobraron
+        if a_favor > 0 {
+            self.registrar_credito_expulsion(&clave, &cuenta, a_favor);
+        }
+
+        let msg = format!(
+            "El usuario {} fue expulsado de la Tanda {}.",
+            &cuenta, &clave
+        );
+        env::log(msg.as_bytes());
+
+        self.revisar_periodos(clave);
+    }
+
+    // ! MÉTODO INTERNO
+    fn revisar_periodos(&mut self, clave: String) {
+        if self.tanda_congelada(&clave) {
+            return;
+        }
+
+        let tanda = self.tandas.get(&clave).unwrap();
+        let num_periodos = self.periodos_tanda.get(&clave).unwrap_or_default().len();
+
+        for n in 0..num_periodos {
+            let periodos = self.periodos_tanda.get(&clave).unwrap();
+            if periodos[n].tanda_pagada || periodos[n].integrantes_pagados.is_empty() {
+                continue;
+            }
+
+            self.cubrir_faltantes(&clave, n);
+
+            // * El sorteo puede asignar al usuario en turno al validar el periodo
+            let periodo_completo = self.validar_pago_tanda(String::from(&clave), n as i32);
+            let periodos = self.periodos_tanda.get(&clave).unwrap();
+
+            if periodo_completo
+                && tanda.pago_automatico
+                && periodos[n].usuario_en_turno != String::new()
+                && self.fondos_disponibles(&clave, n)
+            {
+                self.acreditar_periodo(String::from(&clave), n);
+            }
+        }
+
+        self.registrar_morosos(&clave);
+        self.gestionar_staking(&clave);
+    }
+
+    // ! MÉTODO INTERNO
+    fn disolver_tanda(&mut self, clave: String) {
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        let (liquidaciones, deudas) = self.calcular_liquidacion(&clave);
+
+        // * Los fondos no repartidos quedan comprometidos en los reembolsos
+        for periodo in periodos.iter_mut() {
+            if !periodo.tanda_pagada {
+                periodo.cantidad_recaudada = U128(0);
+                periodo.abonos_parciales.clear();
+            }
+        }
+
+        for liquidacion in liquidaciones.iter() {
+            if liquidacion.reembolso.0 > 0 {
+                self.acreditar_saldo(
+                    &liquidacion.cuenta,
+                    liquidacion.reembolso.0,
+                    ConceptoMovimiento::Reembolso,
+                    String::from(&clave),
+                );
+            }
+        }
+
+        // * Lo prepagado en los monederos regresa completo a cada integrante
+        let mut prepagados: Vec<AccountId> = self
+            .monederos
+            .get(&clave)
+            .map_or(Vec::new(), |monederos| monederos.keys().cloned().collect());
+        prepagados.sort();
+        for cuenta in prepagados.iter() {
+            self.vaciar_monedero(&clave, cuenta);
+        }
+
+        // * Lo que quedó por pagar de los préstamos ya forma parte de las deudas
+        if let Some(mut cartera) = self.prestamos.get(&clave) {
+            for prestamo in cartera.prestamos.iter_mut() {
+                if prestamo.estado == "Vigente" {
+                    prestamo.estado = String::from("Convertido en deuda");
+                }
+            }
+            self.prestamos.insert(&clave, &cartera);
+        }
+
+        tanda.activa = false;
+        tanda.pausada = false;
+        tanda.estado = "Disuelta".to_string();
+
+        self.tandas.insert(&clave, &tanda);
+        self.periodos_tanda.insert(&clave, &periodos);
+        self.liquidaciones.insert(&clave, &liquidaciones);
+        // * Las deudas de la liquidación se suman a las que dejaron las expulsiones
+        if !deudas.is_empty() {
+            let mut registradas = self.deudas.get(&clave).unwrap_or_default();
+            registradas.extend(deudas.iter().cloned());
+            self.deudas.insert(&clave, &registradas);
+        }
+        for deuda in deudas.iter() {
+            self.registrar_incumplimiento(&deuda.deudor, &clave);
+        }
+
+        let msg = format!(
+            "La Tanda {} fue disuelta, se registraron {} deudas pendientes.",
+            &clave,
+            deudas.len()
+        );
+        env::log(msg.as_bytes());
+
+        self.repartir_reserva(clave);
+    }
+
+    // ! MÉTODO INTERNO
+    fn calcular_liquidacion(&self, clave: &String) -> (Vec<Liquidacion>, Vec<Deuda>) {
+        let tanda = self.tandas.get(clave).unwrap();
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+
+        // * Posición de cada cuenta: lo aportado contra lo recibido
+        let mut aportado = HashMap::<AccountId, u128>::new();
+        let mut recibido = HashMap::<AccountId, u128>::new();
+        let mut por_repartir: u128 = 0;
+
+        for integrante in tanda.integrantes.iter() {
+            aportado.insert(String::from(integrante), 0);
+        }
+
+        for periodo in periodos.iter() {
+            let mut pagadores: HashSet<&AccountId> = periodo.aportaciones.keys().collect();
+            pagadores.extend(periodo.abonos_parciales.keys());
+
+            for pagador in pagadores {
+                let monto = periodo.aportado(pagador);
+                *aportado.entry(String::from(pagador)).or_insert(0) += monto;
+
+                if !periodo.tanda_pagada {
+                    por_repartir += monto;
+                }
+            }
+
+            for (beneficiario, monto) in periodo.beneficiarios.iter() {
+                *recibido.entry(String::from(beneficiario)).or_insert(0) += monto.0;
+                aportado.entry(String::from(beneficiario)).or_insert(0);
+            }
+        }
+
+        // * Lo que ya quedó como deuda, por ejemplo al expulsar, no vuelve a cobrarse
+        for deuda in self.deudas.get(clave).unwrap_or_default().iter() {
+            *aportado.entry(String::from(&deuda.deudor)).or_insert(0) += deuda.monto.0;
+            for (acreedor, parte) in deuda.acreedores.iter() {
+                *recibido.entry(String::from(acreedor)).or_insert(0) += parte.0;
+                aportado.entry(String::from(acreedor)).or_insert(0);
+            }
+        }
+
+        // * El capital prestado ya no está en el contrato, cuenta como recibido por el deudor
+        if let Some(cartera) = self.prestamos.get(clave) {
+            for prestamo in cartera
+                .prestamos
+                .iter()
+                .filter(|prestamo| prestamo.estado == "Vigente")
+            {
+                let capital = prestamo.capital_pendiente();
+                *recibido.entry(String::from(&prestamo.deudor)).or_insert(0) += capital;
+                aportado.entry(String::from(&prestamo.deudor)).or_insert(0);
+                por_repartir = por_repartir.saturating_sub(capital);
+            }
+        }
+
+        let mut cuentas: Vec<AccountId> = aportado.keys().cloned().collect();
+        cuentas.sort();
+
+        let total_a_favor: u128 = cuentas
+            .iter()
+            .map(|cuenta| aportado[cuenta].saturating_sub(*recibido.get(cuenta).unwrap_or(&0)))
+            .sum();
+
+        // * Los acreedores se reembolsan a prorrata con los fondos no repartidos
+        let mut liquidaciones = Vec::<Liquidacion>::new();
+        for cuenta in cuentas.iter() {
+            let aportado_cuenta = aportado[cuenta];
+            let recibido_cuenta = *recibido.get(cuenta).unwrap_or(&0);
+            let a_favor = aportado_cuenta.saturating_sub(recibido_cuenta);
+            let reembolso = cmp::min(a_favor, prorrata(por_repartir, a_favor, total_a_favor));
+
+            liquidaciones.push(Liquidacion {
+                cuenta: String::from(cuenta),
+                aportado: U128(aportado_cuenta),
+                recibido: U128(recibido_cuenta),
+                reembolso: U128(reembolso),
+                pendiente_por_cobrar: U128(a_favor - reembolso),
+                deuda: U128(recibido_cuenta.saturating_sub(aportado_cuenta)),
+            });
+        }
+
+        // * Cada deuda se reparte entre los acreedores según lo que quedó pendiente
+        let total_pendiente: u128 = liquidaciones
+            .iter()
+            .map(|liquidacion| liquidacion.pendiente_por_cobrar.0)
+            .sum();
+        let acreedores: Vec<&Liquidacion> = liquidaciones
+            .iter()
+            .filter(|liquidacion| liquidacion.pendiente_por_cobrar.0 > 0)
+            .collect();
+
+        let mut deudas = Vec::<Deuda>::new();
+        for liquidacion in liquidaciones.iter().filter(|l| l.deuda.0 > 0) {
+            let mut deuda = Deuda::new(
+                String::from(&liquidacion.cuenta),
+                String::from(clave),
+                liquidacion.deuda.0,
+            );
+
+            let mut restante = liquidacion.deuda.0;
+            for (n, acreedor) in acreedores.iter().enumerate() {
+                let parte = if n == acreedores.len() - 1 {
+                    restante
+                } else {
+                    prorrata(
+                        liquidacion.deuda.0,
+                        acreedor.pendiente_por_cobrar.0,
+                        total_pendiente,
+                    )
+                };
+                restante -= parte;
+
+                deuda
+                    .acreedores
+                    .insert(String::from(&acreedor.cuenta), U128(parte));
+            }
+
+            deudas.push(deuda);
+        }
+
+        (liquidaciones, deudas)
+    }
+
+    pub fn simular_liquidacion(&self, clave: String) -> Vec<Liquidacion> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.calcular_liquidacion(&clave).0
+    }
+
+    pub fn consultar_liquidacion(&self, clave: String) -> Vec<Liquidacion> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.liquidaciones.get(&clave).unwrap_or_default()
+    }
+
+    pub fn consultar_deudas(&self, clave: String) -> Vec<Deuda> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.deudas.get(&clave).unwrap_or_default()
+    }
+
+    pub fn consultar_propuestas(&self, clave: String) -> Vec<Propuesta> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.propuestas.get(&clave).unwrap_or_default()
+    }
+
+    // * DISPUTAS
+
+    // ! MÉTODO INTERNO
+    fn tanda_congelada(&self, clave: &String) -> bool {
+        self.disputas
+            .get(clave)
+            .unwrap_or_default()
+            .iter()
+            .any(|disputa| disputa.vigente())
+    }
+
+    pub fn asignar_arbitro(&mut self, clave: String, arbitro: AccountId) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        assert!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Sólo el dueño del contrato puede asignar árbitros."
+        );
+
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        tanda.validar_arbitro(&arbitro);
+        tanda.arbitro = Some(String::from(&arbitro));
+        self.tandas.insert(&clave, &tanda);
+
+        let msg = format!("El usuario {} fue asignado como árbitro.", &arbitro);
+        log_generator::create_log("ok", "asignar_arbitro", &clave, &msg);
+    }
+
+    pub fn abrir_disputa(&mut self, clave: String, indice: u32, motivo: String) -> u32 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.integrantes.contains(&id_cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+        assert!(
+            !motivo.is_empty(),
+            "Se debe indicar el motivo de la disputa."
+        );
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        assert!(
+            (indice as usize) < periodos.len(),
+            "El periodo #{} no existe.",
+            indice + 1
+        );
+
+        // * Cada integrante tiene un número limitado de disputas y sólo una abierta a la vez
+        let mut disputas = self.disputas.get(&clave).unwrap_or_default();
+        let promovidas: Vec<&Disputa> = disputas
+            .iter()
+            .filter(|disputa| disputa.promovente == id_cuenta)
+            .collect();
+        assert!(
+            promovidas.len() < MAX_DISPUTAS_INTEGRANTE,
+            "El usuario {} ya abrió las {} disputas permitidas en esta Tanda.",
+            &id_cuenta,
+            MAX_DISPUTAS_INTEGRANTE
+        );
+        assert!(
+            !promovidas.iter().any(|disputa| disputa.vigente()),
+            "El usuario {} ya tiene una disputa abierta en esta Tanda.",
+            &id_cuenta
+        );
+
+        let id = disputas.len() as u32;
+        disputas.push(Disputa::new(id, indice, motivo));
+        self.disputas.insert(&clave, &disputas);
+
+        if tanda.arbitro.is_none() {
+            env::log(
+                "La Tanda no tiene árbitro, el dueño del contrato deberá asignar uno.".as_bytes(),
+            );
+        }
+
+        let msg = format!(
+            "El usuario {} abrió la disputa #{} sobre el periodo #{}, los pagos quedan congelados.",
+            &id_cuenta,
+            id,
+            indice + 1
+        );
+        log_generator::create_log("ok", "abrir_disputa", &clave, &msg);
+
+        id
+    }
+
+    pub fn resolver_disputa(
+        &mut self,
+        clave: String,
+        id: u32,
+        dictamen: String,
+        medidas: Vec<MedidaArbitral>,
+    ) -> Disputa {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.arbitro.as_ref() == Some(&id_cuenta),
+            "El usuario {} no es el árbitro de esta Tanda.",
+            &id_cuenta
+        );
+        tanda.validar_arbitro(&id_cuenta);
+
+        let mut disputas = self.disputas.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < disputas.len(),
+            "La disputa #{} no existe.",
+            id
+        );
+        assert!(
+            disputas[id as usize].estado == "Abierta",
+            "La disputa #{} ya fue cerrada.",
+            id
+        );
+        assert!(
+            disputas[id as usize].vigente(),
+            "La disputa #{} venció el {}.",
+            id,
+            &disputas[id as usize].vencimiento
+        );
+
+        let indice = disputas[id as usize].indice_periodo as usize;
+        for medida in medidas.iter() {
+            self.aplicar_medida(&clave, indice, medida.clone());
+        }
+
+        let disputa = &mut disputas[id as usize];
+        disputa.estado = String::from("Resuelta");
+        disputa.resolucion = Some(Resolucion {
+            arbitro: String::from(&id_cuenta),
+            dictamen,
+            medidas,
+            fecha: date_handling::calcular_inicio(),
+        });
+        let disputa = disputa.clone();
+        self.disputas.insert(&clave, &disputas);
+
+        let msg = format!("El árbitro {} resolvió la disputa #{}.", &id_cuenta, id);
+        log_generator::create_log("ok", "resolver_disputa", &clave, &msg);
+
+        self.revisar_periodos(clave);
+        disputa
+    }
+
+    pub fn retirar_disputa(&mut self, clave: String, id: u32) -> Disputa {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let id_cuenta = env::predecessor_account_id();
+
+        let mut disputas = self.disputas.get(&clave).unwrap_or_default();
+        assert!(
+            (id as usize) < disputas.len(),
+            "La disputa #{} no existe.",
+            id
+        );
+
+        let disputa = &mut disputas[id as usize];
+        assert!(
+            disputa.promovente == id_cuenta,
+            "Sólo el usuario {} puede retirar la disputa #{}.",
+            &disputa.promovente,
+            id
+        );
+        assert!(
+            disputa.estado == "Abierta",
+            "La disputa #{} ya fue cerrada.",
+            id
+        );
+
+        disputa.estado = String::from("Retirada");
+        let disputa = disputa.clone();
+        self.disputas.insert(&clave, &disputas);
+
+        let msg = format!("El usuario {} retiró la disputa #{}.", &id_cuenta, id);
+        log_generator::create_log("ok", "retirar_disputa", &clave, &msg);
+
+        self.revisar_periodos(clave);
+        disputa
+    }
+
+    // ! MÉTODO INTERNO
+    fn aplicar_medida(&mut self, clave: &String, indice: usize, medida: MedidaArbitral) {
+        let tanda = self.tandas.get(clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(clave).unwrap();
+        let referencia = format!("{}#{}", clave, indice + 1);
+        let periodo = &mut periodos[indice];
+
+        assert!(
+            !periodo.tanda_pagada,
+            "El periodo #{} ya fue pagado, no puede modificarse.",
+            indice + 1
+        );
+
+        let mut reembolso: Option<(AccountId, u128)> = None;
+        match medida {
+            // * El pago se hizo fuera de la cadena, no se espera su cuota en el contrato
+            MedidaArbitral::MarcarPagado { cuenta } => {
+                assert!(
+                    tanda.integrantes.contains(&cuenta),
+                    "El usuario {} no es integrante de esta tanda.",
+                    &cuenta
+                );
+
+                if let Some(abonado) = periodo.abonos_parciales.remove(&cuenta) {
+                    reembolso = Some((String::from(&cuenta), abonado.0));
+                }
+                if !periodo.integrantes_pagados.contains(&cuenta) {
+                    periodo.pagos_externos.insert(String::from(&cuenta));
+                    periodo.integrantes_pagados.insert(cuenta);
+                }
+            }
+            MedidaArbitral::DesmarcarPagado { cuenta } => {
+                periodo.integrantes_pagados.remove(&cuenta);
+                periodo.pagos_externos.remove(&cuenta);
+                periodo.pagos_completos = false;
+
+                if let Some(aportado) = periodo.aportaciones.remove(&cuenta) {
+                    periodo.cantidad_recaudada =
+                        U128(periodo.cantidad_recaudada.0.saturating_sub(aportado.0));
+                    reembolso = Some((cuenta, aportado.0));
+                }
+            }
+            MedidaArbitral::ReasignarTurno {
+                cuenta,
+                num_asiento,
+            } => {
+                assert!(
+                    tanda.integrantes.contains(&cuenta),
+                    "El usuario {} no es integrante de esta tanda.",
+                    &cuenta
+                );
+
+                // * El pago sigue al asiento en turno, así que el asiento cambia con la cuenta
+                let asientos: Vec<u32> = tanda
+                    .asientos
+                    .iter()
+                    .filter(|asiento| asiento.participaciones.contains_key(&cuenta))
+                    .map(|asiento| asiento.numero)
+                    .collect();
+                let asiento_en_turno = match num_asiento {
+                    Some(numero) => {
+                        assert!(
+                            asientos.contains(&numero),
+                            "El asiento {} no pertenece a {}.",
+                            numero,
+                            &cuenta
+                        );
+                        Some(numero)
+                    }
+                    None => {
+                        assert!(
+                            asientos.len() <= 1,
+                            "El usuario {} tiene {} asientos, se debe indicar cuál recibe el turno.",
+                            &cuenta,
+                            asientos.len()
+                        );
+                        asientos.first().copied()
+                    }
+                };
+
+                periodo.asiento_en_turno = asiento_en_turno;
+                periodo.usuario_en_turno = cuenta;
+            }
+            // * Sólo se reembolsa lo que la cuenta aportó al periodo, lo que quede cuenta como abono
+            MedidaArbitral::Reembolsar { cuenta, monto } => {
+                let aportado = periodo.aportaciones.get(&cuenta).map_or(0, |monto| monto.0);
+                assert!(
+                    aportado > 0,
+                    "El usuario {} no tiene aportaciones en el periodo #{}.",
+                    &cuenta,
+                    indice + 1
+                );
+                assert!(
+                    monto.0 > 0 && monto.0 <= aportado,
+                    "El reembolso debe estar entre 1 y {} yoctoNEAR, lo aportado por {}.",
+                    aportado,
+                    &cuenta
+                );
+                assert!(
+                    monto.0 == aportado || tanda.pagos_parciales,
+                    "Esta Tanda no acepta pagos parciales, se debe reembolsar la aportación completa."
+                );
+
+                periodo.aportaciones.remove(&cuenta);
+                periodo.integrantes_pagados.remove(&cuenta);
+                periodo.cantidad_recaudada = U128(periodo.cantidad_recaudada.0 - aportado);
+                periodo.pagos_completos = false;
+                if monto.0 < aportado {
+                    periodo
+                        .abonos_parciales
+                        .insert(String::from(&cuenta), U128(aportado - monto.0));
+                }
+                reembolso = Some((cuenta, monto.0));
+            }
+        }
+
+        self.periodos_tanda.insert(clave, &periodos);
+
+        if let Some((cuenta, monto)) = reembolso {
+            self.acreditar_saldo(&cuenta, monto, ConceptoMovimiento::Reembolso, referencia);
+        }
+    }
+
+    pub fn consultar_disputas(&self, clave: String) -> Vec<Disputa> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.disputas.get(&clave).unwrap_or_default()
+    }
+
+    // * STAKING
+
+    // Sólo el dueño del contrato decide en qué pools pueden delegar las Tandas.
+    pub fn agregar_pool_staking(&mut self, pool: AccountId) {
+        assert!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Sólo el dueño del contrato puede administrar los pools de staking."
+        );
+        let mut estado_pool = self.pools_staking.get(&pool).unwrap_or_default();
+        estado_pool.habilitado = true;
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        let msg = format!("El pool {} fue habilitado para staking.", &pool);
+        env::log(msg.as_bytes());
+    }
+
+    // Los fondos ya delegados al pool todavía pueden desdelegarse y retirarse.
+    pub fn quitar_pool_staking(&mut self, pool: AccountId) {
+        assert!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Sólo el dueño del contrato puede administrar los pools de staking."
+        );
+        let mut estado_pool = self.pools_staking.get(&pool).unwrap_or_default();
+        assert!(
+            estado_pool.habilitado,
+            "El pool {} no está habilitado para staking.",
+            &pool
+        );
+        estado_pool.habilitado = false;
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        let msg = format!("El pool {} dejó de estar habilitado para staking.", &pool);
+        env::log(msg.as_bytes());
+    }
+
+    pub fn consultar_pools_staking(&self) -> Vec<AccountId> {
+        self.pools_staking
+            .iter()
+            .filter(|(_, estado_pool)| estado_pool.habilitado)
+            .map(|(pool, _)| pool)
+            .collect()
+    }
+
+    // ! MÉTODO INTERNO
+    fn validar_pool_habilitado(&self, pool: &AccountId) {
+        assert!(
+            self.pools_staking
+                .get(pool)
+                .is_some_and(|estado_pool| estado_pool.habilitado),
+            "El pool {} no está habilitado para staking.",
+            pool
+        );
+    }
+
+    // ! MÉTODO INTERNO
+    fn validar_cambio_pool(&self, clave: &String, opciones: &OpcionesTanda) {
+        if let Some(pool) = &opciones.pool_staking {
+            assert!(
+                !self.tandas.get(clave).unwrap().activa,
+                "El pool de staking no puede cambiarse después de activar la Tanda."
+            );
+            assert!(
+                self.staking
+                    .get(clave)
+                    .map_or(0, |staking| staking.inmovilizado())
+                    == 0,
+                "No se puede cambiar el pool de staking mientras haya fondos delegados."
+            );
+            if !pool.is_empty() {
+                self.validar_pool_habilitado(pool);
+            }
+        }
+    }
+
+    // ! MÉTODO INTERNO
+    fn fondos_disponibles(&self, clave: &String, indice: usize) -> bool {
+        let tanda = self.tandas.get(clave).unwrap();
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+        let inmovilizado = self
+            .staking
+            .get(clave)
+            .map_or(0, |staking| staking.inmovilizado());
+
+        // * Lo prestado a los receptores del periodo se descuenta de su pago
+        let receptores: Vec<AccountId> = match periodos[indice].asiento_en_turno {
+            Some(numero) => tanda.asientos[numero as usize - 1]
+                .participaciones
+                .keys()
+                .cloned()
+                .collect(),
+            None => vec![String::from(&periodos[indice].usuario_en_turno)],
+        };
+        let prestado = self
+            .prestamos
+            .get(clave)
+            .map_or(0, |cartera| cartera.prestado(&receptores));
+
+        if inmovilizado + prestado == 0 {
+            return true;
+        }
+
+        let fondos: u128 = periodos
+            .iter()
+            .filter(|periodo| !periodo.tanda_pagada)
+            .map(|periodo| periodo.cantidad_recaudada.0)
+            .sum();
+
+        periodos[indice].cantidad_recaudada.0 + inmovilizado + prestado <= fondos
+    }
+
+    // ! MÉTODO INTERNO
+    fn fondos_ociosos(&self, clave: &String) -> u128 {
+        let ociosos: u128 = self
+            .periodos_tanda
+            .get(clave)
+            .unwrap_or_default()
+            .iter()
+            .filter(|periodo| {
+                !periodo.tanda_pagada
+                    && !periodo.pagos_completos
+                    && !date_handling::periodo_por_vencer(&periodo.fin, DIAS_DESDELEGACION)
+            })
+            .map(|periodo| periodo.cantidad_recaudada.0)
+            .sum();
+        // * Lo que ya se desdelegó pertenece a periodos por pagar y regresa con el retiro
+        let depositado = self
+            .staking
+            .get(clave)
+            .map_or(0, |staking| staking.depositado.0);
+        let prestado = self
+            .prestamos
+            .get(clave)
+            .map_or(0, |cartera| cartera.prestado(&[]));
+
+        ociosos.saturating_sub(depositado + prestado)
+    }
+
+    // ! MÉTODO INTERNO
+    // Lo que falta en el contrato para pagar los periodos completos o por vencer, lo que ya se
+    // desdelegó y espera a retirarse cuenta como disponible.
+    fn fondos_por_desdelegar(&self, clave: &String) -> u128 {
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+        let staking = self.staking.get(clave).unwrap_or_default();
+
+        let fondos: u128 = periodos
+            .iter()
+            .filter(|periodo| !periodo.tanda_pagada)
+            .map(|periodo| periodo.cantidad_recaudada.0)
+            .sum();
+        let requeridos: u128 = periodos
+            .iter()
+            .filter(|periodo| {
+                !periodo.tanda_pagada
+                    && (periodo.pagos_completos
+                        || date_handling::periodo_por_vencer(&periodo.fin, DIAS_DESDELEGACION))
+            })
+            .map(|periodo| periodo.cantidad_recaudada.0)
+            .sum();
+        let prestado = self
+            .prestamos
+            .get(clave)
+            .map_or(0, |cartera| cartera.prestado(&[]));
+
+        let en_contrato = fondos.saturating_sub(staking.depositado.0 + prestado);
+        cmp::min(requeridos.saturating_sub(en_contrato), staking.depositado.0)
+    }
+
+    // ! MÉTODO INTERNO
+    fn preparar_staking(&mut self, clave: &String) -> (AccountId, EstadoStaking) {
+        assert!(self.tandas.get(clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.integrantes.contains(&id_cuenta) || tanda.rol(&id_cuenta).is_some(),
+            "El usuario {} no participa en esta Tanda.",
+            &id_cuenta
+        );
+        assert!(
+            tanda.pool_staking.is_some(),
+            "La Tanda no tiene un pool de staking configurado."
+        );
+
+        self.bloquear_pool(clave)
+    }
+
+    // ! MÉTODO INTERNO
+    // Bloquea el pool de la Tanda hasta que termine la operación, el saldo del contrato en el
+    // pool se comparte entre Tandas y debe consultarse sin operaciones intermedias.
+    fn bloquear_pool(&mut self, clave: &String) -> (AccountId, EstadoStaking) {
+        let pool = self.tandas.get(clave).unwrap().pool_staking.unwrap();
+        let mut estado_pool = self.pools_staking.get(&pool).unwrap_or_default();
+        assert!(
+            !estado_pool.operacion_pendiente,
+            "Hay una operación de staking en curso con el pool {}, intenta más tarde.",
+            &pool
+        );
+
+        estado_pool.operacion_pendiente = true;
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        (pool, self.staking.get(clave).unwrap_or_default())
+    }
+
+    // ! MÉTODO INTERNO
+    fn liberar_pool(&mut self, clave: &String) -> (AccountId, EstadoPool) {
+        let pool = self.tandas.get(clave).unwrap().pool_staking.unwrap();
+        let mut estado_pool = self.pools_staking.get(&pool).unwrap_or_default();
+        estado_pool.operacion_pendiente = false;
+
+        (pool, estado_pool)
+    }
+
+    // Delega al pool los fondos recaudados de periodos que aún no terminan.
+    pub fn delegar_fondos(&mut self, clave: String) -> Promise {
+        let (pool, mut staking) = self.preparar_staking(&clave);
+
+        self.validar_pool_habilitado(&pool);
+        let monto = self.fondos_ociosos(&clave);
+        assert!(monto > 0, "No hay fondos ociosos para delegar.");
+
+        staking.depositado = U128(staking.depositado.0 + monto);
+        self.staking.insert(&clave, &staking);
+
+        let msg = format!("Se delegan {} yoctoNEAR al pool {}.", monto, &pool);
+        log_generator::create_log("ok", "delegar_fondos", &clave, &msg);
+
+        ext_pool::get_account_staked_balance(env::current_account_id(), &pool, 0, GAS_CONSULTA)
+            .then(ext_self::resolver_consulta_delegacion(
+                clave,
+                U128(monto),
+                &env::current_account_id(),
+                0,
+                GAS_STAKING + 2 * GAS_CALLBACK,
+            ))
+    }
+
+    // El monto es principal delegado y sale del pool con su rendimiento, sin monto se retira
+    // todo lo que corresponde a la Tanda.
+    pub fn desdelegar_fondos(&mut self, clave: String, monto: Option<U128>) -> Promise {
+        let (pool, staking) = self.preparar_staking(&clave);
+        assert!(
+            staking.acciones.0 > 0,
+            "La Tanda no tiene fondos delegados al pool."
+        );
+        if let Some(monto) = &monto {
+            assert!(
+                monto.0 > 0 && monto.0 <= staking.depositado.0,
+                "El monto a retirar del pool debe estar entre 1 y {} yoctoNEAR, lo delegado por la Tanda.",
+                staking.depositado.0
+            );
+        }
+
+        self.iniciar_desdelegacion(clave, &pool, monto)
+    }
+
+    // ! MÉTODO INTERNO
+    fn iniciar_desdelegacion(
+        &mut self,
+        clave: String,
+        pool: &AccountId,
+        monto: Option<U128>,
+    ) -> Promise {
+        ext_pool::get_account_staked_balance(env::current_account_id(), pool, 0, GAS_CONSULTA).then(
+            ext_self::resolver_consulta_desdelegacion(
+                clave,
+                monto,
+                &env::current_account_id(),
+                0,
+                GAS_STAKING + 2 * GAS_CALLBACK,
+            ),
+        )
+    }
+
+    // Recupera en el contrato lo que ya terminó el periodo de espera del pool.
+    pub fn retirar_fondos_staking(&mut self, clave: String) -> Promise {
+        let (pool, staking) = self.preparar_staking(&clave);
+
+        let principal = staking.principal_por_retirar.0;
+        let rendimiento = staking.rendimiento_por_retirar.0;
+        assert!(
+            principal + rendimiento > 0,
+            "No hay fondos pendientes de retirar del pool."
+        );
+
+        let epoca_retiro = self.pools_staking.get(&pool).unwrap().epoca_retiro.0;
+        assert!(
+            env::epoch_height() >= epoca_retiro,
+            "Los fondos podrán retirarse del pool a partir de la época {}.",
+            epoca_retiro
+        );
+
+        self.iniciar_retiro_staking(clave, &pool, staking)
+    }
+
+    // ! MÉTODO INTERNO
+    fn iniciar_retiro_staking(
+        &mut self,
+        clave: String,
+        pool: &AccountId,
+        mut staking: EstadoStaking,
+    ) -> Promise {
+        let principal = staking.principal_por_retirar.0;
+        let rendimiento = staking.rendimiento_por_retirar.0;
+
+        staking.principal_por_retirar = U128(0);
+        staking.rendimiento_por_retirar = U128(0);
+        self.staking.insert(&clave, &staking);
+
+        ext_pool::withdraw(U128(principal + rendimiento), pool, 0, GAS_STAKING).then(
+            ext_self::resolver_retiro_staking(
+                clave,
+                U128(principal),
+                U128(rendimiento),
+                &env::current_account_id(),
+                0,
+                GAS_CALLBACK,
+            ),
+        )
+    }
+
+    // ! MÉTODO INTERNO
+    // Trae de vuelta los fondos de los periodos completos o por vencer, y todo lo delegado al
+    // terminar la Tanda, sin esperar a que un integrante lo solicite. Se omite mientras otra
+    // operación use el pool o si la llamada no tiene gas suficiente.
+    fn gestionar_staking(&mut self, clave: &String) {
+        let tanda = self.tandas.get(clave).unwrap();
+        let (pool, staking) = match (tanda.pool_staking, self.staking.get(clave)) {
+            (Some(pool), Some(staking)) => (pool, staking),
+            _ => return,
+        };
+        let estado_pool = self.pools_staking.get(&pool).unwrap_or_default();
+        if estado_pool.operacion_pendiente {
+            return;
+        }
+
+        let por_retirar = staking.principal_por_retirar.0 + staking.rendimiento_por_retirar.0;
+        let monto = if !tanda.activa {
+            staking.depositado.0
+        } else {
+            self.fondos_por_desdelegar(clave)
+        };
+
+        // * Un nuevo retiro del pool reinicia la espera de lo que ya se desdelegó
+        let (operacion, gas) = if por_retirar > 0 {
+            if env::epoch_height() < estado_pool.epoca_retiro.0 {
+                return;
+            }
+            ("retirar", GAS_STAKING + GAS_CALLBACK)
+        } else if staking.acciones.0 > 0 && monto > 0 {
+            ("desdelegar", GAS_CONSULTA + GAS_STAKING + 2 * GAS_CALLBACK)
+        } else {
+            return;
+        };
+
+        if env::prepaid_gas() - env::used_gas() < gas + GAS_CALLBACK {
+            let msg = format!(
+                "No hay gas suficiente para {} los fondos del pool {}, se intentará en la siguiente operación con al menos {} TGas.",
+                operacion,
+                &pool,
+                (gas + GAS_CALLBACK) / 1_000_000_000_000
+            );
+            log_generator::create_log("error", "gestionar_staking", clave, &msg);
+            return;
+        }
+
+        let (pool, staking) = self.bloquear_pool(clave);
+        if por_retirar > 0 {
+            self.iniciar_retiro_staking(String::from(clave), &pool, staking);
+        } else {
+            let monto = if monto == staking.depositado.0 {
+                None
+            } else {
+                Some(U128(monto))
+            };
+            self.iniciar_desdelegacion(String::from(clave), &pool, monto);
+        }
+    }
+
+    // ! MÉTODO INTERNO
+    fn resultado_staking(&self, clave: &str) -> bool {
+        assert_eq!(
+            env::promise_results_count(),
+            1,
+            "Se esperaba el resultado de una operación de staking."
+        );
+
+        let exito = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        if !exito {
+            log_generator::create_log(
+                "error",
+                "staking",
+                clave,
+                "La operación con el pool de staking falló, se revirtió el registro.",
+            );
+        }
+
+        exito
+    }
+
+    // ! MÉTODO INTERNO
+    fn saldo_pool(&self) -> Option<u128> {
+        match env::promise_result(0) {
+            PromiseResult::Successful(valor) => serde_json::from_slice::<U128>(&valor)
+                .ok()
+                .map(|saldo| saldo.0),
+            _ => None,
+        }
+    }
+
+    #[private]
+    pub fn resolver_consulta_delegacion(
+        &mut self,
+        clave: String,
+        monto: U128,
+    ) -> PromiseOrValue<bool> {
+        let exito = self.resultado_staking(&clave);
+        let (pool, estado_pool) = self.liberar_pool(&clave);
+
+        match self.saldo_pool().filter(|_| exito) {
+            Some(saldo) => {
+                // * Las acciones se calculan con el saldo del contrato antes del depósito
+                let acciones = if estado_pool.acciones.0 == 0 || saldo == 0 {
+                    monto.0
+                } else {
+                    prorrata(monto.0, estado_pool.acciones.0, saldo)
+                };
+
+                PromiseOrValue::Promise(
+                    ext_pool::deposit_and_stake(&pool, monto.0, GAS_STAKING).then(
+                        ext_self::resolver_delegacion(
+                            clave,
+                            monto,
+                            U128(acciones),
+                            &env::current_account_id(),
+                            0,
+                            GAS_CALLBACK,
+                        ),
+                    ),
+                )
+            }
+            None => {
+                let mut staking = self.staking.get(&clave).unwrap_or_default();
+                staking.depositado = U128(staking.depositado.0 - monto.0);
+                self.staking.insert(&clave, &staking);
+                self.pools_staking.insert(&pool, &estado_pool);
+
+                PromiseOrValue::Value(false)
+            }
+        }
+    }
+
+    #[private]
+    pub fn resolver_delegacion(&mut self, clave: String, monto: U128, acciones: U128) -> bool {
+        let exito = self.resultado_staking(&clave);
+        let (pool, mut estado_pool) = self.liberar_pool(&clave);
+        let mut staking = self.staking.get(&clave).unwrap_or_default();
+
+        if exito {
+            staking.acciones = U128(staking.acciones.0 + acciones.0);
+            estado_pool.acciones = U128(estado_pool.acciones.0 + acciones.0);
+        } else {
+            staking.depositado = U128(staking.depositado.0 - monto.0);
+        }
+        self.staking.insert(&clave, &staking);
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        exito
+    }
+
+    #[private]
+    pub fn resolver_consulta_desdelegacion(
+        &mut self,
+        clave: String,
+        monto: Option<U128>,
+    ) -> PromiseOrValue<bool> {
+        let exito = self.resultado_staking(&clave);
+        let (pool, mut estado_pool) = self.liberar_pool(&clave);
+        let mut staking = self.staking.get(&clave).unwrap_or_default();
+
+        let saldo = match self.saldo_pool().filter(|_| exito) {
+            Some(saldo) if saldo > 0 && staking.acciones.0 > 0 => saldo,
+            _ => {
+                self.pools_staking.insert(&pool, &estado_pool);
+                return PromiseOrValue::Value(false);
+            }
+        };
+
+        // * El monto es principal, las acciones que lo cubren llevan además su rendimiento
+        let acciones = match monto {
+            Some(monto) => cmp::min(
+                staking.acciones.0,
+                prorrata(monto.0, staking.acciones.0, staking.depositado.0) + 1,
+            ),
+            None => staking.acciones.0,
+        };
+        // * Lo que vale la parte de la Tanda se calcula con el saldo que reporta el pool
+        let valor = prorrata(saldo, acciones, estado_pool.acciones.0);
+        let depositado = if acciones == staking.acciones.0 {
+            staking.depositado.0
+        } else {
+            prorrata(staking.depositado.0, acciones, staking.acciones.0)
+        };
+        let principal = cmp::min(depositado, valor);
+        let rendimiento = valor - principal;
+
+        staking.acciones = U128(staking.acciones.0 - acciones);
+        staking.depositado = U128(staking.depositado.0 - depositado);
+        staking.principal_por_retirar = U128(staking.principal_por_retirar.0 + principal);
+        staking.rendimiento_por_retirar = U128(staking.rendimiento_por_retirar.0 + rendimiento);
+        staking.rendimiento = U128(staking.rendimiento.0 + rendimiento);
+        self.staking.insert(&clave, &staking);
+
+        estado_pool.acciones = U128(estado_pool.acciones.0 - acciones);
+        estado_pool.operacion_pendiente = true;
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        let msg = format!(
+            "Se desdelegan {} yoctoNEAR del pool {}, {} de rendimiento.",
+            valor, &pool, rendimiento
+        );
+        log_generator::create_log("ok", "desdelegar_fondos", &clave, &msg);
+
+        PromiseOrValue::Promise(ext_pool::unstake(U128(valor), &pool, 0, GAS_STAKING).then(
+            ext_self::resolver_desdelegacion(
+                clave,
+                Desdelegacion {
+                    acciones: U128(acciones),
+                    depositado: U128(depositado),
+                    principal: U128(principal),
+                    rendimiento: U128(rendimiento),
+                },
+                &env::current_account_id(),
+                0,
+                GAS_CALLBACK,
+            ),
+        ))
+    }
+
+    #[private]
+    pub fn resolver_desdelegacion(&mut self, clave: String, desdelegacion: Desdelegacion) -> bool {
+        let exito = self.resultado_staking(&clave);
+        let (pool, mut estado_pool) = self.liberar_pool(&clave);
+        let mut staking = self.staking.get(&clave).unwrap_or_default();
+
+        if exito {
+            estado_pool.epoca_retiro = U64(env::epoch_height() + EPOCAS_DESDELEGACION);
+        } else {
+            let Desdelegacion {
+                acciones,
+                depositado,
+                principal,
+                rendimiento,
+            } = desdelegacion;
+
+            staking.acciones = U128(staking.acciones.0 + acciones.0);
+            staking.depositado = U128(staking.depositado.0 + depositado.0);
+            staking.principal_por_retirar = U128(staking.principal_por_retirar.0 - principal.0);
+            staking.rendimiento_por_retirar =
+                U128(staking.rendimiento_por_retirar.0 - rendimiento.0);
+            staking.rendimiento = U128(staking.rendimiento.0 - rendimiento.0);
+            estado_pool.acciones = U128(estado_pool.acciones.0 + acciones.0);
+        }
+        self.staking.insert(&clave, &staking);
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        exito
+    }
+
+    #[private]
+    pub fn resolver_retiro_staking(
+        &mut self,
+        clave: String,
+        principal: U128,
+        rendimiento: U128,
+    ) -> bool {
+        let exito = self.resultado_staking(&clave);
+        let (pool, estado_pool) = self.liberar_pool(&clave);
+        let mut staking = self.staking.get(&clave).unwrap_or_default();
+
+        if !exito {
+            staking.principal_por_retirar = U128(staking.principal_por_retirar.0 + principal.0);
+            staking.rendimiento_por_retirar =
+                U128(staking.rendimiento_por_retirar.0 + rendimiento.0);
+        }
+        self.staking.insert(&clave, &staking);
+        self.pools_staking.insert(&pool, &estado_pool);
+
+        if exito {
+            // * Con los fondos de vuelta se pueden pagar los periodos pendientes
+            self.revisar_periodos(String::from(&clave));
+            self.repartir_rendimiento(clave);
+        }
+
+        exito
+    }
+
+    // ! MÉTODO INTERNO
+    fn repartir_rendimiento(&mut self, clave: String) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut staking = match self.staking.get(&clave) {
+            Some(staking) => staking,
+            None => return,
+        };
+
+        // * Sólo al final del ciclo y con todo el rendimiento de vuelta en el contrato
+        if tanda.estado != "Finalizada"
+            || staking.rendimiento_repartido
+            || staking.rendimiento.0 == 0
+            || staking.inmovilizado() > 0
+            || staking.rendimiento_por_retirar.0 > 0
+        {
+            return;
+        }
+
+        let total = staking.rendimiento.0;
+        let num_integrantes =
+            self.repartir_entre_integrantes(&tanda, total, ConceptoMovimiento::Rendimiento);
+
+        staking.rendimiento_repartido = true;
+        self.staking.insert(&clave, &staking);
+
+        let msg = format!(
+            "Se repartieron {} yoctoNEAR de rendimiento entre {} integrantes.",
+            total, num_integrantes
+        );
+        log_generator::create_log("ok", "repartir_rendimiento", &clave, &msg);
+    }
+
+    // ! MÉTODO INTERNO
+    // Reparte según la participación de cada integrante, el último recibe el residuo del redondeo.
+    fn repartir_entre_integrantes(
+        &mut self,
+        tanda: &Tanda,
+        total: u128,
+        concepto: ConceptoMovimiento,
+    ) -> usize {
+        let mut integrantes: Vec<&AccountId> = tanda.integrantes.iter().collect();
+        integrantes.sort();
+
+        let mut restante = total;
+        for (n, integrante) in integrantes.iter().enumerate() {
+            let parte = if n == integrantes.len() - 1 {
+                restante
+            } else {
+                prorrata(
+                    total,
+                    tanda.participacion(integrante) as u128,
+                    tanda.num_integrantes as u128 * PARTICIPACION_COMPLETA as u128,
+                )
+            };
+            restante -= parte;
+
+            self.acreditar_saldo(integrante, parte, concepto.clone(), String::from(&tanda.id));
+        }
+
+        integrantes.len()
+    }
+
+    pub fn consultar_staking(&self, clave: String) -> EstadoStaking {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.staking.get(&clave).unwrap_or_default()
+    }
+
+    // * CAJA DE AHORRO
+
+    // ! MÉTODO INTERNO
+    fn ahorro_por_integrante(&self, tanda: &Tanda, periodos: &[Periodo]) -> Vec<(AccountId, u128)> {
+        let mut ahorradores: Vec<(AccountId, u128)> = tanda
+            .integrantes
+            .iter()
+            .map(|cuenta| {
+                let ahorrado = periodos
+                    .iter()
+                    .map(|periodo| periodo.aportado(cuenta))
+                    .sum();
+                (String::from(cuenta), ahorrado)
+            })
+            .collect();
+        ahorradores.sort();
+
+        ahorradores
+    }
+
+    // ! MÉTODO INTERNO
+    fn meta_alcanzada(&self, tanda: &Tanda, periodos: &[Periodo]) -> bool {
+        let meta = tanda.ahorro.as_ref().map_or(0, |caja| caja.meta);
+        let ahorrado: u128 = self
+            .ahorro_por_integrante(tanda, periodos)
+            .iter()
+            .map(|(_, ahorrado)| ahorrado)
+            .sum();
+
+        meta > 0 && ahorrado >= one_near() * meta as u128
+    }
+
+    pub fn retirar_ahorro(&mut self, clave: String) -> U128 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(
+            tanda.es_caja_ahorro(),
+            "La Tanda {} no es una caja de ahorro.",
+            &clave
+        );
+        assert!(tanda.activa, "La caja de ahorro no está activa.");
+        assert!(
+            tanda.integrantes.contains(&id_cuenta),
+            "El usuario {} no es integrante de esta caja de ahorro.",
+            &id_cuenta
+        );
+        assert!(
+            !self.tanda_congelada(&clave),
+            "Los pagos de esta Tanda están congelados por una disputa abierta."
+        );
+
+        // * Al vencer ya no hay retiro anticipado, cada ahorrador cobra sin penalización
+        let periodos = self.periodos_tanda.get(&clave).unwrap();
+        assert!(
+            !date_handling::fecha_alcanzada(&tanda.fecha_final)
+                && !self.meta_alcanzada(&tanda, &periodos),
+            "La caja de ahorro ya venció o alcanzó su meta, se paga sin penalización con liquidar_caja_ahorro."
+        );
+
+        self.retirar_de_caja(clave, id_cuenta)
+    }
+
+    // ! MÉTODO INTERNO
+    fn retirar_de_caja(&mut self, clave: String, cuenta: AccountId) -> U128 {
+        let mut tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+        let mut caja = tanda.ahorro.clone().unwrap();
+
+        let ahorrado: u128 = periodos
+            .iter()
+            .map(|periodo| periodo.aportado(&cuenta))
+            .sum();
+        assert!(
+            self.staking
+                .get(&clave)
+                .map_or(0, |staking| staking.inmovilizado())
+                == 0,
+            "Primero deben retirarse los fondos delegados al pool de staking."
+        );
+
+        // * La penalización se queda para los demás, el último ahorrador se lleva lo acumulado
+        let (penalizacion, bono) = if tanda.integrantes.len() > 1 {
+            (ahorrado * caja.penalizacion_retiro as u128 / 100, 0)
+        } else {
+            (0, caja.fondo_retiros.0)
+        };
+
+        for periodo in periodos.iter_mut() {
+            if let Some(aportacion) = periodo.aportaciones.remove(&cuenta) {
+                periodo.cantidad_recaudada = U128(periodo.cantidad_recaudada.0 - aportacion.0);
+            }
+            periodo.abonos_parciales.remove(&cuenta);
+        }
+
+        let mut vacios = tanda.quitar_integrante(&cuenta);
+        vacios.sort_unstable();
+        for numero in vacios.iter().rev() {
+            tanda.eliminar_asiento(*numero);
+        }
+
+        caja.fondo_retiros = U128(caja.fondo_retiros.0 + penalizacion - bono);
+        caja.retirados.insert(String::from(&cuenta));
+        tanda.ahorro = Some(caja);
+
+        self.tandas.insert(&clave, &tanda);
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        let reembolso = ahorrado - penalizacion + bono;
+        self.acreditar_saldo(
+            &cuenta,
+            reembolso,
+            ConceptoMovimiento::RetiroAhorro,
+            String::from(&clave),
+        );
+
+        self.vaciar_monedero(&clave, &cuenta);
+
+        if let Some(mut usuario) = self.usuarios.get(&cuenta) {
+            usuario.tandas_inscritas.retain(|id| id != &clave);
+            self.usuarios.insert(&cuenta, &usuario);
+        }
+
+        let msg = format!(
+            "El usuario {} retiró {} yoctoNEAR de su ahorro con una penalización de {} yoctoNEAR.",
+            &cuenta, reembolso, penalizacion
+        );
+        log_generator::create_log("ok", "retirar_ahorro", &clave, &msg);
+
+        self.revisar_periodos(clave);
+
+        U128(reembolso)
+    }
+
+    // Paga a cada ahorrador lo que aportó más su parte de las penalizaciones por retiro.
+    pub fn liquidar_caja_ahorro(&mut self, clave: String) {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(&clave).unwrap();
+
+        assert!(
+            tanda.es_caja_ahorro(),
+            "La Tanda {} no es una caja de ahorro.",
+            &clave
+        );
+        assert!(tanda.activa, "La caja de ahorro no está activa.");
+        assert!(
+            !self.tanda_congelada(&clave),
+            "Los pagos de esta Tanda están congelados por una disputa abierta."
+        );
+        assert!(
+            self.staking
+                .get(&clave)
+                .map_or(0, |staking| staking.inmovilizado())
+                == 0,
+            "Primero deben retirarse los fondos delegados al pool de staking."
+        );
+        assert!(
+            date_handling::fecha_alcanzada(&tanda.fecha_final)
+                || self.meta_alcanzada(&tanda, &periodos),
+            "La caja de ahorro aún no vence ni alcanza su meta."
+        );
+
+        let ahorradores = self.ahorro_por_integrante(&tanda, &periodos);
+        let total: u128 = ahorradores.iter().map(|(_, ahorrado)| ahorrado).sum();
+        let fondo = tanda.ahorro.as_ref().unwrap().fondo_retiros.0;
+        let ultimo = periodos.len() - 1;
+
+        let mut restante = fondo;
+        for (n, (cuenta, ahorrado)) in ahorradores.iter().enumerate() {
+            let parte = if n == ahorradores.len() - 1 {
+                restante
+            } else {
+                prorrata(fondo, *ahorrado, total)
+            };
+            restante -= parte;
+
+            for periodo in periodos.iter_mut() {
+                let aportado = periodo.aportado(cuenta);
+                if aportado > 0 {
+                    periodo
+                        .beneficiarios
+                        .insert(String::from(cuenta), U128(aportado));
+                }
+            }
+            if parte > 0 {
+                let recibido = periodos[ultimo]
+                    .beneficiarios
+                    .get(cuenta)
+                    .map_or(0, |recibido| recibido.0);
+                periodos[ultimo]
+                    .beneficiarios
+                    .insert(String::from(cuenta), U128(recibido + parte));
+            }
+
+            self.acreditar_saldo(
+                cuenta,
+                ahorrado + parte,
+                ConceptoMovimiento::PagoTanda,
+                String::from(&clave),
+            );
+
+            let msg = format!(
+                "El usuario {} recibió {} yoctoNEAR de su ahorro y {} yoctoNEAR de penalizaciones.",
+                cuenta, ahorrado, parte
+            );
+            env::log(msg.as_bytes());
+        }
+
+        for periodo in periodos.iter_mut() {
+            periodo.abonos_parciales.clear();
+            periodo.tanda_pagada = true;
+        }
+        self.periodos_tanda.insert(&clave, &periodos);
+
+        let msg = format!(
+            "Se liquidaron {} yoctoNEAR entre {} ahorradores.",
+            total + fondo,
+            ahorradores.len()
+        );
+        log_generator::create_log("ok", "liquidar_caja_ahorro", &clave, &msg);
+
+        self.cerrar_ciclo(clave);
+    }
+
+    pub fn consultar_ahorro(&self, clave: String) -> ResumenAhorro {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+
+        assert!(
+            tanda.es_caja_ahorro(),
+            "La Tanda {} no es una caja de ahorro.",
+            &clave
+        );
+        let caja = tanda.ahorro.clone().unwrap();
+
+        let ahorradores = self.ahorro_por_integrante(&tanda, &periodos);
+        let meta_alcanzada = self.meta_alcanzada(&tanda, &periodos);
+
+        ResumenAhorro {
+            meta: U128(one_near() * caja.meta as u128),
+            ahorrado: U128(ahorradores.iter().map(|(_, ahorrado)| ahorrado).sum()),
+            fecha_vencimiento: String::from(&tanda.fecha_final),
+            fondo_retiros: caja.fondo_retiros,
+            ahorradores: ahorradores
+                .into_iter()
+                .map(|(cuenta, ahorrado)| (cuenta, U128(ahorrado)))
+                .collect(),
+            retirados: caja.retirados,
+            meta_alcanzada,
+            liquidable: tanda.activa
+                && (meta_alcanzada || date_handling::fecha_alcanzada(&tanda.fecha_final)),
+        }
+    }
+
+    // * PRÉSTAMOS
+
+    // El límite es lo que el integrante aún debe aportar a la Tanda.
+    pub fn limite_prestamo(&self, clave: String, id_cuenta: Option<String>) -> U128 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+
+        let periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+
+        let pendiente: u128 = periodos
+            .iter()
+            .filter(|periodo| !periodo.integrantes_pagados.contains(&cuenta))
+            .map(|periodo| tanda.cuota_monto(&cuenta, periodo.monto))
+            .sum();
+
+        // * El préstamo y su interés se descuentan del siguiente pago que recibe la cuenta
+        let pago = periodos
+            .iter()
+            .filter(|periodo| !periodo.tanda_pagada)
+            .map(|periodo| tanda.pago_turno(&cuenta, periodo))
+            .find(|pago| *pago > 0)
+            .unwrap_or(0);
+        let cobrable = prorrata(pago, 100, 100 + tanda.tasa_prestamo as u128);
+
+        U128(cmp::min(pendiente, cobrable))
+    }
+
+    pub fn solicitar_prestamo(&mut self, clave: String, monto: U128) -> u32 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let id_cuenta = env::predecessor_account_id();
+
+        assert!(tanda.prestamos, "Esta Tanda no otorga préstamos.");
+        assert!(
+            !tanda.es_caja_ahorro(),
+            "Las cajas de ahorro no otorgan préstamos."
+        );
+        assert!(tanda.activa, "La Tanda no está activa.");
+        assert!(!tanda.pausada, "La Tanda se encuentra pausada.");
+        assert!(
+            !self.tanda_congelada(&clave),
+            "Los pagos de esta Tanda están congelados por una disputa abierta."
+        );
+        assert!(
+            tanda.integrantes.contains(&id_cuenta),
+            "El usuario {} no es integrante de esta tanda.",
+            &id_cuenta
+        );
+        assert!(
+            !self.integrante_en_mora(&clave, &id_cuenta),
+            "El usuario {} tiene pagos atrasados.",
+            &id_cuenta
+        );
+
+        let mut cartera = self.prestamos.get(&clave).unwrap_or_default();
+        assert!(
+            cartera.vigente(&id_cuenta).is_none(),
+            "El usuario {} ya tiene un préstamo vigente.",
+            &id_cuenta
+        );
+
+        assert!(
+            monto.0 > 0,
+            "El monto del préstamo tiene que ser mayor a 0."
+        );
+        let periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        assert!(
+            periodos
+                .iter()
+                .any(|periodo| !periodo.tanda_pagada && tanda.pago_turno(&id_cuenta, periodo) > 0),
+            "El usuario {} no tiene un turno pendiente de cobro.",
+            &id_cuenta
+        );
+        let limite = self
+            .limite_prestamo(String::from(&clave), Some(String::from(&id_cuenta)))
+            .0;
+        assert!(
+            monto.0 <= limite,
+            "El préstamo excede el límite de {} yoctoNEAR.",
+            limite
+        );
+        let disponible = self.fondos_ociosos(&clave);
+        assert!(
+            monto.0 <= disponible,
+            "La Tanda sólo tiene {} yoctoNEAR disponibles para prestar.",
+            disponible
+        );
+
+        let id = cartera.prestamos.len() as u32 + 1;
+        let interes = monto.0 * tanda.tasa_prestamo as u128 / 100;
+        let prestamo = Prestamo::new(
+            id,
+            String::from(&id_cuenta),
+            monto.0,
+            interes,
+            tanda.plazo_prestamo,
+        );
+
+        let msg = format!(
+            "El usuario {} recibió un préstamo de {} yoctoNEAR con {} yoctoNEAR de interés, vence el {}.",
+            &id_cuenta, monto.0, interes, &prestamo.vencimiento
+        );
+
+        cartera.prestamos.push(prestamo);
+        self.prestamos.insert(&clave, &cartera);
+
+        self.acreditar_saldo(
+            &id_cuenta,
+            monto.0,
+            ConceptoMovimiento::Prestamo,
+            String::from(&clave),
+        );
+
+        log_generator::create_log("ok", "solicitar_prestamo", &clave, &msg);
+
+        id
+    }
+
+    #[payable]
+    pub fn pagar_prestamo(&mut self, clave: String, id: u32) -> U128 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let mut cartera = self.prestamos.get(&clave).unwrap_or_default();
+
+        assert!(
+            id > 0 && id as usize <= cartera.prestamos.len(),
+            "El préstamo {} no existe.",
+            id
+        );
+        let prestamo = &cartera.prestamos[id as usize - 1];
+        assert!(
+            prestamo.estado == "Vigente",
+            "El préstamo ya fue liquidado."
+        );
+        // * Sólo el deudor abona, así cada abono queda a su nombre
+        assert!(
+            env::predecessor_account_id() == prestamo.deudor,
+            "Sólo {} puede abonar al préstamo {}.",
+            prestamo.deudor,
+            id
+        );
+
+        let abono = env::attached_deposit();
+        assert!(abono > 0, "El abono tiene que ser mayor a 0.");
+        assert!(
+            abono <= prestamo.pendiente(),
+            "El abono excede lo pendiente del préstamo ({} yoctoNEAR).",
+            prestamo.pendiente()
+        );
+
+        let intereses = cartera.prestamos[id as usize - 1].abonar(abono);
+        let pendiente = cartera.prestamos[id as usize - 1].pendiente();
+        cartera.intereses_por_repartir = U128(cartera.intereses_por_repartir.0 + intereses);
+        self.prestamos.insert(&clave, &cartera);
+
+        let msg = format!(
+            "Se abonaron {} yoctoNEAR al préstamo {}, restan {} yoctoNEAR.",
+            abono, id, pendiente
+        );
+        log_generator::create_log("ok", "pagar_prestamo", &clave, &msg);
+
+        // * Con el capital de vuelta se pueden pagar los periodos pendientes
+        self.revisar_periodos(String::from(&clave));
+        self.repartir_intereses(clave);
+
+        U128(pendiente)
+    }
+
+    // ! MÉTODO INTERNO
+    // Cobra el préstamo vigente del receptor con su pago y devuelve lo descontado.
+    fn descontar_prestamo(&mut self, clave: &String, cuenta: &AccountId, pago: u128) -> u128 {
+        let mut cartera = match self.prestamos.get(clave) {
+            Some(cartera) => cartera,
+            None => return 0,
+        };
+        let n = match cartera.vigente(cuenta) {
+            Some(n) => n,
+            None => return 0,
+        };
+
+        let descuento = cmp::min(pago, cartera.prestamos[n].pendiente());
+        let intereses = cartera.prestamos[n].abonar(descuento);
+        cartera.intereses_por_repartir = U128(cartera.intereses_por_repartir.0 + intereses);
+        self.prestamos.insert(clave, &cartera);
+
+        let msg = format!(
+            "Se descontaron {} yoctoNEAR del pago de {} para el préstamo {}.",
+            descuento, cuenta, cartera.prestamos[n].id
+        );
+        log_generator::create_log("ok", "descontar_prestamo", clave, &msg);
+
+        descuento
+    }
+
+    // ! MÉTODO INTERNO
+    fn repartir_intereses(&mut self, clave: String) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut cartera = match self.prestamos.get(&clave) {
+            Some(cartera) => cartera,
+            None => return,
+        };
+
+        // * Los intereses cobrados se reparten al final del ciclo
+        let total = cartera.intereses_por_repartir.0;
+        if tanda.estado != "Finalizada" || total == 0 {
+            return;
+        }
+
+        cartera.intereses_por_repartir = U128(0);
+        self.prestamos.insert(&clave, &cartera);
+
+        let num_integrantes =
+            self.repartir_entre_integrantes(&tanda, total, ConceptoMovimiento::Intereses);
+
+        let msg = format!(
+            "Se repartieron {} yoctoNEAR de intereses entre {} integrantes.",
+            total, num_integrantes
+        );
+        log_generator::create_log("ok", "repartir_intereses", &clave, &msg);
+    }
+
+    pub fn consultar_prestamos(&self, clave: String) -> Vec<Prestamo> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.prestamos.get(&clave).unwrap_or_default().prestamos
+    }
+
+    // * FONDO DE RESERVA
+
+    // Cubre con la reserva a los integrantes que no pagaron un periodo ya vencido.
+    pub fn cubrir_con_reserva(&mut self, clave: String, indice: u32) -> bool {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+        let periodos = self.periodos_tanda.get(&clave).unwrap_or_default();
+        let n = indice as usize;
+
+        assert!(tanda.reserva > 0, "Esta Tanda no tiene fondo de reserva.");
+        assert!(tanda.activa, "La Tanda no está activa.");
+        assert!(
+            !self.tanda_congelada(&clave),
+            "Los pagos de esta Tanda están congelados por una disputa abierta."
+        );
+        assert!(
+            n < periodos.len(),
+            "La tanda sólo contiene {} periodos.",
+            periodos.len()
+        );
+        assert!(
+            date_handling::periodo_vencido(&periodos[n].fin),
+            "El periodo #{} aún no vence.",
+            n + 1
+        );
+        assert!(
+            !periodos[n].pagos_completos,
+            "El periodo #{} ya está completo.",
+            n + 1
+        );
+
+        let cubierto = self.cubrir_faltantes(&clave, n);
+        if cubierto {
+            self.completar_periodo(clave, n);
+        }
+
+        cubierto
+    }
+
+    // ! MÉTODO INTERNO
+    // Cubre a todos los morosos que no pagan en efectivo o a ninguno si la reserva no alcanza.
+    fn cubrir_faltantes(&mut self, clave: &String, indice: usize) -> bool {
+        let tanda = self.tandas.get(clave).unwrap();
+        let mut periodos = self.periodos_tanda.get(clave).unwrap();
+
+        if tanda.reserva == 0
+            || tanda.es_caja_ahorro()
+            || periodos[indice].pagos_completos
+            || !date_handling::periodo_vencido(&periodos[indice].fin)
+        {
+            return false;
+        }
+
+        let mut morosos: Vec<(AccountId, u128)> = tanda
+            .integrantes
+            .iter()
+            .filter(|cuenta| !periodos[indice].integrantes_pagados.contains(*cuenta))
+            .map(|cuenta| {
+                let cuota = tanda.cuota_monto(cuenta, periodos[indice].monto);
+                (String::from(cuenta), periodos[indice].adeudo(cuenta, cuota))
+            })
+            .collect();
+        morosos.sort();
+
+        // * Quien paga en efectivo no aporta a la reserva y tampoco se le cubre con ella
+        morosos.retain(|(cuenta, _)| {
+            let en_efectivo = periodos
+                .iter()
+                .any(|periodo| periodo.pagos_externos.contains(cuenta));
+            if en_efectivo {
+                let msg = format!(
+                    "La reserva no cubre a {}, que ha pagado en efectivo, el periodo #{} sigue incompleto.",
+                    cuenta,
+                    indice + 1
+                );
+                log_generator::create_log("error", "cubrir_con_reserva", clave, &msg);
+            }
+            !en_efectivo
+        });
+        if morosos.is_empty() {
+            return false;
+        }
+
+        let faltante: u128 = morosos.iter().map(|(_, adeudo)| adeudo).sum();
+        let mut fondo = self.reservas.get(clave).unwrap_or_default();
+        if faltante > fondo.saldo.0 {
+            let msg = format!(
+                "La reserva ({} yoctoNEAR) no alcanza para cubrir el faltante de {} yoctoNEAR del periodo #{}.",
+                fondo.saldo.0,
+                faltante,
+                indice + 1
+            );
+            log_generator::create_log("error", "cubrir_con_reserva", clave, &msg);
+            return false;
+        }
+
+        for (moroso, adelanto) in morosos.iter() {
+            let abonado = periodos[indice]
+                .abonos_parciales
+                .remove(moroso)
+                .map_or(0, |abonado| abonado.0);
+
+            periodos[indice]
+                .integrantes_pagados
+                .insert(String::from(moroso));
+            periodos[indice].cantidad_recaudada =
+                U128(periodos[indice].cantidad_recaudada.0 + abonado + adelanto);
+            if abonado > 0 {
+                periodos[indice]
+                    .aportaciones
+                    .insert(String::from(moroso), U128(abonado));
+            }
+
+            fondo.adelantos.push(Adelanto::new(
+                String::from(moroso),
+                indice as u32,
+                *adelanto,
+            ));
+            self.registrar_incumplimiento(moroso, clave);
+
+            let msg = format!(
+                "La reserva cubrió {} yoctoNEAR que {} no pagó en el periodo #{}.",
+                adelanto,
+                moroso,
+                indice + 1
+            );
+            log_generator::create_log("ok", "cubrir_con_reserva", clave, &msg);
+        }
+
+        fondo.saldo = U128(fondo.saldo.0 - faltante);
+        self.reservas.insert(clave, &fondo);
+        self.periodos_tanda.insert(clave, &periodos);
+
+        true
+    }
+
+    #[payable]
+    pub fn reembolsar_reserva(&mut self, clave: String) -> U128 {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let id_cuenta = env::predecessor_account_id();
+        let mut fondo = self.reservas.get(&clave).unwrap_or_default();
+
+        let pendiente = fondo.pendiente(&id_cuenta);
+        assert!(
+            pendiente > 0,
+            "El usuario {} no tiene adeudos con la reserva.",
+            &id_cuenta
+        );
+
+        let abono = env::attached_deposit();
+        assert!(abono > 0, "El abono tiene que ser mayor a 0.");
+        assert!(
+            abono <= pendiente,
+            "El abono excede el adeudo con la reserva ({} yoctoNEAR).",
+            pendiente
+        );
+
+        // * Se cubren primero los adelantos más antiguos
+        let mut restante = abono;
+        for adelanto in fondo
+            .adelantos
+            .iter_mut()
+            .filter(|adelanto| adelanto.deudor == id_cuenta)
+        {
+            let parte = cmp::min(restante, adelanto.pendiente());
+            adelanto.pagado = U128(adelanto.pagado.0 + parte);
+            restante -= parte;
+        }
+        fondo.saldo = U128(fondo.saldo.0 + abono);
+        self.reservas.insert(&clave, &fondo);
+
+        let msg = format!(
+            "El usuario {} reembolsó {} yoctoNEAR a la reserva, restan {} yoctoNEAR.",
+            &id_cuenta,
+            abono,
+            pendiente - abono
+        );
+        log_generator::create_log("ok", "reembolsar_reserva", &clave, &msg);
+
+        self.limpiar_incumplimiento(&clave, &id_cuenta);
+        self.repartir_reserva(clave);
+
+        U128(pendiente - abono)
+    }
+
+    // ! MÉTODO INTERNO
+    fn repartir_reserva(&mut self, clave: String) {
+        let tanda = self.tandas.get(&clave).unwrap();
+        let mut fondo = match self.reservas.get(&clave) {
+            Some(fondo) => fondo,
+            None => return,
+        };
+
+        // * Lo que sobra de la reserva regresa a los integrantes al cerrar la Tanda
+        let total = fondo.saldo.0;
+        if (tanda.estado != "Finalizada" && tanda.estado != "Disuelta") || total == 0 {
+            return;
+        }
+
+        fondo.saldo = U128(0);
+        self.reservas.insert(&clave, &fondo);
+
+        // * Cada integrante recibe según lo que aportó, los pagos en efectivo no aportan
+        let mut aportantes: Vec<(&AccountId, u128)> = tanda
+            .integrantes
+            .iter()
+            .map(|cuenta| {
+                let aportado = fondo
+                    .aportaciones
+                    .get(cuenta)
+                    .map_or(0, |aportado| aportado.0);
+                (cuenta, aportado)
+            })
+            .filter(|(_, aportado)| *aportado > 0)
+            .collect();
+        aportantes.sort();
+
+        let num_integrantes = if aportantes.is_empty() {
+            self.repartir_entre_integrantes(&tanda, total, ConceptoMovimiento::Reserva)
+        } else {
+            let total_aportado: u128 = aportantes.iter().map(|(_, aportado)| aportado).sum();
+            let mut restante = total;
+            for (n, (cuenta, aportado)) in aportantes.iter().enumerate() {
+                let parte = if n == aportantes.len() - 1 {
+                    restante
+                } else {
+                    prorrata(total, *aportado, total_aportado)
+                };
+                restante -= parte;
+
+                self.acreditar_saldo(
+                    cuenta,
+                    parte,
+                    ConceptoMovimiento::Reserva,
+                    String::from(&clave),
+                );
+            }
+            aportantes.len()
+        };
+
+        let msg = format!(
+            "Se repartieron {} yoctoNEAR de la reserva entre {} integrantes.",
+            total, num_integrantes
+        );
+        log_generator::create_log("ok", "repartir_reserva", &clave, &msg);
+    }
+
+    pub fn consultar_reserva(&self, clave: String) -> FondoReserva {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        self.reservas.get(&clave).unwrap_or_default()
+    }
+
+    // * REGISTRO DE INCUMPLIMIENTOS
+
+    // ! MÉTODO INTERNO
+    fn validar_ingreso(&self, tanda: &Tanda, cuenta: &AccountId) {
+        let impedimento = self.impedimento_ingreso(tanda, cuenta);
+        assert!(impedimento.is_none(), "{}", impedimento.unwrap_or_default());
+    }
+
+    // ! MÉTODO INTERNO
+    fn impedimento_ingreso(&self, tanda: &Tanda, cuenta: &AccountId) -> Option<String> {
+        if tanda.bloquear_morosos {
+            let pendientes = self.incumplimientos.get(cuenta).unwrap_or_default();
+            if !pendientes.is_empty() {
+                return Some(format!(
+                    "El usuario {} tiene incumplimientos sin resolver en las Tandas {:?}.",
+                    cuenta, pendientes
+                ));
+            }
+        }
+
+        for tipo in tanda.atestaciones_requeridas.iter() {
+            if !self.atestacion_vigente(cuenta, tipo) {
+                return Some(format!(
+                    "El usuario {} no cuenta con la atestación vigente \"{}\" que requiere esta Tanda.",
+                    cuenta, tipo
+                ));
+            }
+        }
+
+        None
+    }
+
+    // ! MÉTODO INTERNO
+    fn registrar_incumplimiento(&mut self, cuenta: &AccountId, clave: &String) {
+        let mut pendientes = self.incumplimientos.get(cuenta).unwrap_or_default();
+        if pendientes.contains(clave) {
+            return;
+        }
+        pendientes.push(String::from(clave));
+        self.incumplimientos.insert(cuenta, &pendientes);
+
+        let msg = format!(
+            "Se registró un incumplimiento de {} en la Tanda {}.",
+            cuenta, clave
+        );
+        log_generator::create_log("ok", "registrar_incumplimiento", clave, &msg);
+    }
+
+    // ! MÉTODO INTERNO
+    // Quien ya recibió su pago y deja vencer un periodo queda en el registro de incumplimientos.
+    fn registrar_morosos(&mut self, clave: &String) {
+        let tanda = self.tandas.get(clave).unwrap();
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+
+        let mut morosos: Vec<&AccountId> = tanda
+            .integrantes
+            .iter()
+            .filter(|cuenta| {
+                periodos
+                    .iter()
+                    .any(|periodo| periodo.beneficiarios.contains_key(*cuenta))
+                    && periodos.iter().any(|periodo| {
+                        !periodo.integrantes_pagados.contains(*cuenta)
+                            && date_handling::periodo_vencido(&periodo.fin)
+                    })
+            })
+            .collect();
+        morosos.sort();
+
+        for moroso in morosos {
+            self.registrar_incumplimiento(moroso, clave);
+        }
+    }
+
+    // ! MÉTODO INTERNO
+    // La Tanda sale del registro de la cuenta cuando no le quedan deudas ni pagos atrasados.
+    fn limpiar_incumplimiento(&mut self, clave: &String, cuenta: &AccountId) {
+        let mut registro = self.incumplimientos.get(cuenta).unwrap_or_default();
+        if !registro.contains(clave) {
+            return;
+        }
+
+        let tanda = self.tandas.get(clave).unwrap();
+        let deuda_pendiente = self
+            .deudas
+            .get(clave)
+            .unwrap_or_default()
+            .iter()
+            .any(|deuda| &deuda.deudor == cuenta && !deuda.saldada);
+        let en_mora = tanda.activa
+            && tanda.integrantes.contains(cuenta)
+            && self.integrante_en_mora(clave, cuenta);
+        if deuda_pendiente || en_mora {
+            return;
+        }
+
+        registro.retain(|tanda| tanda != clave);
+        if registro.is_empty() {
+            self.incumplimientos.remove(cuenta);
+        } else {
+            self.incumplimientos.insert(cuenta, &registro);
+        }
+
+        let msg = format!(
+            "Se retiró el incumplimiento de {} en la Tanda {}.",
+            cuenta, clave
+        );
+        log_generator::create_log("ok", "limpiar_incumplimiento", clave, &msg);
+    }
+
+    // ! MÉTODO INTERNO
+    fn registrar_deuda_expulsion(&mut self, clave: &String, cuenta: &AccountId, monto: u128) {
+        let tanda = self.tandas.get(clave).unwrap();
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+
+        // * Los afectados son los integrantes que aún no reciben su pago
+        let mut acreedores: Vec<&AccountId> = tanda
+            .integrantes
+            .iter()
+            .filter(|integrante| {
+                !periodos
+                    .iter()
+                    .any(|periodo| periodo.beneficiarios.contains_key(*integrante))
+            })
+            .collect();
+        if acreedores.is_empty() {
+            return;
+        }
+        acreedores.sort();
+
+        let total: u128 = acreedores
+            .iter()
+            .map(|acreedor| tanda.participacion(acreedor) as u128)
+            .sum();
+        let mut deuda = Deuda::new(String::from(cuenta), String::from(clave), monto);
+
+        let mut restante = monto;
+        for (n, acreedor) in acreedores.iter().enumerate() {
+            let parte = if n == acreedores.len() - 1 {
+                restante
+            } else {
+                prorrata(monto, tanda.participacion(acreedor) as u128, total)
+            };
+            restante -= parte;
+
+            deuda
+                .acreedores
+                .insert(String::from(*acreedor), U128(parte));
+        }
+
+        let mut deudas = self.deudas.get(clave).unwrap_or_default();
+        deudas.push(deuda);
+        self.deudas.insert(clave, &deudas);
+
+        self.registrar_incumplimiento(cuenta, clave);
+    }
+
+    // ! MÉTODO INTERNO
+    fn registrar_credito_expulsion(&mut self, clave: &String, cuenta: &AccountId, monto: u128) {
+        let periodos = self.periodos_tanda.get(clave).unwrap_or_default();
+
+        // * Cada beneficiario debe la parte de lo aportado por el expulsado en su turno
+        let mut cobrado = HashMap::<AccountId, u128>::new();
+        for periodo in periodos.iter().filter(|periodo| periodo.tanda_pagada) {
+            let aportado = periodo.aportado(cuenta);
+            let total: u128 = periodo.beneficiarios.values().map(|monto| monto.0).sum();
+            for (beneficiario, recibido) in periodo.beneficiarios.iter() {
+                if beneficiario != cuenta && aportado > 0 {
+                    *cobrado.entry(String::from(beneficiario)).or_insert(0) +=
+                        prorrata(aportado, recibido.0, total);
+                }
+            }
+        }
+
+        let mut deudores: Vec<&AccountId> = cobrado.keys().collect();
+        if deudores.is_empty() {
+            return;
+        }
+        deudores.sort();
+
+        let total: u128 = cobrado.values().sum();
+        let mut deudas = self.deudas.get(clave).unwrap_or_default();
+        let mut restante = monto;
+        for (n, deudor) in deudores.iter().enumerate() {
+            let parte = if n == deudores.len() - 1 {
+                restante
+            } else {
+                prorrata(monto, cobrado[*deudor], total)
+            };
+            restante -= parte;
+
+            let mut deuda = Deuda::new(String::from(*deudor), String::from(clave), parte);
+            deuda.acreedores.insert(String::from(cuenta), U128(parte));
+            deudas.push(deuda);
+        }
+        self.deudas.insert(clave, &deudas);
+
+        let msg = format!(
+            "Se registraron {} yoctoNEAR a favor de {} que ya se habían pagado a otros turnos.",
+            monto, cuenta
+        );
+        log_generator::create_log("ok", "expulsar_integrante", clave, &msg);
+    }
+
+    // El pago se reenvía a los acreedores y limpia el registro del deudor en esa Tanda.
+    #[payable]
+    pub fn saldar_deuda(&mut self, clave: String) {
+        let id_cuenta = env::predecessor_account_id();
+        let mut deudas = self.deudas.get(&clave).unwrap_or_default();
+
+        let pendientes: Vec<usize> = deudas
+            .iter()
+            .enumerate()
+            .filter(|(_, deuda)| deuda.deudor == id_cuenta && !deuda.saldada)
+            .map(|(n, _)| n)
+            .collect();
+        assert!(
+            !pendientes.is_empty(),
+            "El usuario {} no tiene deudas pendientes en la Tanda {}.",
+            &id_cuenta,
+            &clave
+        );
+
+        let total: u128 = pendientes.iter().map(|n| deudas[*n].monto.0).sum();
+        assert!(
+            env::attached_deposit() == total,
+            "El depósito debe ser igual a la deuda pendiente ({} yoctoNEAR).",
+            total
+        );
+
+        for n in pendientes {
+            for (acreedor, parte) in deudas[n].acreedores.iter() {
+                self.acreditar_saldo(
+                    acreedor,
+                    parte.0,
+                    ConceptoMovimiento::CobroDeuda,
+                    String::from(&clave),
+                );
+            }
+        }
+        for deuda in deudas.iter_mut().filter(|deuda| deuda.deudor == id_cuenta) {
+            deuda.saldada = true;
+        }
+        self.deudas.insert(&clave, &deudas);
+        self.limpiar_incumplimiento(&clave, &id_cuenta);
+
+        let msg = format!(
+            "El usuario {} saldó {} yoctoNEAR de deuda, se reenviaron a sus acreedores.",
+            &id_cuenta, total
+        );
+        log_generator::create_log("ok", "saldar_deuda", &clave, &msg);
+    }
+
+    pub fn consultar_incumplimientos(&self, id_cuenta: Option<String>) -> Vec<Deuda> {
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+
+        self.incumplimientos
+            .get(&cuenta)
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|clave| self.deudas.get(clave).unwrap_or_default())
+            .filter(|deuda| deuda.deudor == cuenta && !deuda.saldada)
+            .collect()
+    }
+
+    // * ATESTACIONES
+
+    pub fn agregar_verificador(&mut self, cuenta: AccountId) {
+        assert!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Sólo el dueño del contrato puede administrar verificadores."
+        );
+        self.verificadores.insert(&cuenta);
+
+        let msg = format!("El usuario {} fue registrado como verificador.", &cuenta);
+        env::log(msg.as_bytes());
+    }
+
+    // Las atestaciones que emitió dejan de ser válidas.
+    pub fn quitar_verificador(&mut self, cuenta: AccountId) {
+        assert!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Sólo el dueño del contrato puede administrar verificadores."
+        );
+        assert!(
+            self.verificadores.remove(&cuenta),
+            "El usuario {} no es verificador.",
+            &cuenta
+        );
+
+        let msg = format!("El usuario {} dejó de ser verificador.", &cuenta);
+        env::log(msg.as_bytes());
+    }
+
+    pub fn consultar_verificadores(&self) -> Vec<AccountId> {
+        self.verificadores.to_vec()
+    }
+
+    pub fn emitir_atestacion(&mut self, cuenta: AccountId, tipo: String, vigencia: Option<u32>) {
+        let verificador = env::predecessor_account_id();
+        assert!(
+            self.verificadores.contains(&verificador),
+            "El usuario {} no es un verificador autorizado.",
+            &verificador
+        );
+        assert!(
+            !tipo.is_empty(),
+            "El tipo de atestación no puede estar vacío."
+        );
+
+        // * Una nueva atestación reemplaza a la anterior del mismo verificador y tipo
+        let mut atestaciones = self.atestaciones.get(&cuenta).unwrap_or_default();
+        atestaciones
+            .retain(|atestacion| atestacion.tipo != tipo || atestacion.verificador != verificador);
+        atestaciones.push(Atestacion::new(String::from(&tipo), vigencia));
+        self.atestaciones.insert(&cuenta, &atestaciones);
+
+        let msg = format!(
+            "El verificador {} emitió la atestación \"{}\" para {}.",
+            &verificador, &tipo, &cuenta
+        );
+        env::log(msg.as_bytes());
+    }
+
+    pub fn revocar_atestacion(&mut self, cuenta: AccountId, tipo: String) {
+        let verificador = env::predecessor_account_id();
+        let mut atestaciones = self.atestaciones.get(&cuenta).unwrap_or_default();
+
+        let atestacion = atestaciones.iter_mut().find(|atestacion| {
+            atestacion.tipo == tipo && atestacion.verificador == verificador && !atestacion.revocada
+        });
+        assert!(
+            atestacion.is_some(),
+            "El verificador {} no ha emitido la atestación \"{}\" para {}.",
+            &verificador,
+            &tipo,
+            &cuenta
+        );
+        atestacion.unwrap().revocada = true;
+        self.atestaciones.insert(&cuenta, &atestaciones);
+
+        let msg = format!(
+            "El verificador {} revocó la atestación \"{}\" de {}.",
+            &verificador, &tipo, &cuenta
+        );
+        env::log(msg.as_bytes());
+    }
+
+    // ! MÉTODO INTERNO
+    fn atestacion_vigente(&self, cuenta: &AccountId, tipo: &str) -> bool {
+        self.atestaciones
+            .get(cuenta)
+            .unwrap_or_default()
+            .iter()
+            .any(|atestacion| {
+                atestacion.tipo == tipo
+                    && atestacion.vigente()
+                    && self.verificadores.contains(&atestacion.verificador)
+            })
+    }
+
+    pub fn consultar_atestaciones(&self, id_cuenta: Option<String>) -> Vec<Atestacion> {
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+        self.atestaciones.get(&cuenta).unwrap_or_default()
+    }
+
+    // * EXPOSICIÓN
+
+    pub fn configurar_limite_exposicion(&mut self, limite: u32) {
+        assert!(
+            env::predecessor_account_id() == env::current_account_id(),
+            "Sólo el dueño del contrato puede configurar el límite de exposición."
+        );
+        self.limite_exposicion = limite;
+
+        let msg = format!(
+            "El límite global de exposición es ahora de {} NEAR cada {} días.",
+            limite, DIAS_EXPOSICION
+        );
+        env::log(msg.as_bytes());
+    }
+
+    // ! MÉTODO INTERNO
+    // Lleva la cuota de la cuenta al periodo de referencia, así una Tanda semanal y una mensual
+    // se comparan por lo que cuestan en el mismo lapso.
+    fn compromiso(&self, tanda: &Tanda, cuenta: &AccountId) -> u128 {
+        prorrata(tanda.cuota(cuenta), DIAS_EXPOSICION, tanda.periodo as u128)
+    }
+
+    // ! MÉTODO INTERNO
+    // Compromiso de la cuenta en cada Tanda pendiente, activa o pausada en la que participa.
+    fn compromisos(&self, cuenta: &AccountId) -> HashMap<String, u128> {
+        let inscritas = self
+            .usuarios
+            .get(cuenta)
+            .map_or(Vec::new(), |usuario| usuario.tandas_inscritas);
+
+        inscritas
+            .iter()
+            .filter_map(|clave| self.tandas.get(clave))
+            .filter(|tanda| {
+                (tanda.estado == "Pendiente"
+                    || tanda.estado == "Activa"
+                    || tanda.estado == "Pausada")
+                    && tanda.integrantes.contains(cuenta)
+            })
+            .map(|tanda| (String::from(&tanda.id), self.compromiso(&tanda, cuenta)))
+            .collect()
+    }
+
+    // ! MÉTODO INTERNO
+    // Ciclos terminados sin incumplimientos pendientes, cualquier deuda la deja en 0.
+    fn reputacion(&self, cuenta: &AccountId) -> u32 {
+        if self.incumplimientos.get(cuenta).is_some() {
+            return 0;
+        }
+
+        self.usuarios
+            .get(cuenta)
+            .map_or(Vec::new(), |usuario| usuario.tandas_inscritas)
+            .iter()
+            .filter_map(|clave| self.tandas.get(clave))
+            .filter(|tanda| tanda.estado == "Finalizada" && tanda.integrantes.contains(cuenta))
+            .count() as u32
+    }
+
+    // ! MÉTODO INTERNO
+    fn validar_exposicion(&self, tanda: &Tanda, cuenta: &AccountId) {
+        let exceso = self.exceso_exposicion(tanda, cuenta);
+        assert!(exceso.is_none(), "{}", exceso.unwrap_or_default());
+    }
+
+    // ! MÉTODO INTERNO
+    fn exceso_exposicion(&self, tanda: &Tanda, cuenta: &AccountId) -> Option<String> {
+        let mut compromisos = self.compromisos(cuenta);
+        compromisos.insert(String::from(&tanda.id), self.compromiso(tanda, cuenta));
+        let total: u128 = compromisos.values().sum();
+
+        if self.limite_exposicion > 0 {
+            let limite = one_near() * self.limite_exposicion as u128;
+            if total > limite {
+                return Some(format!(
+                    "El usuario {} tendría {} yoctoNEAR comprometidos cada {} días, el límite global es de {} yoctoNEAR.",
+                    cuenta, total, DIAS_EXPOSICION, limite
+                ));
+            }
+        }
+
+        if tanda.limite_exposicion > 0 {
+            // * Cada ciclo terminado aumenta el límite un 10%, hasta duplicarlo
+            let escala = if tanda.escalar_por_reputacion {
+                100 + 10 * cmp::min(self.reputacion(cuenta), 10)
+            } else {
+                100
+            };
+            let limite = one_near() * tanda.limite_exposicion as u128 * escala as u128 / 100;
+            if total > limite {
+                return Some(format!(
+                    "El usuario {} tendría {} yoctoNEAR comprometidos cada {} días, esta Tanda permite hasta {} yoctoNEAR.",
+                    cuenta, total, DIAS_EXPOSICION, limite
+                ));
+            }
+        }
+
+        None
+    }
+
+    pub fn consultar_exposicion(&self, id_cuenta: Option<String>) -> Exposicion {
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+        let compromisos = self.compromisos(&cuenta);
+
+        Exposicion {
+            cuenta: String::from(&cuenta),
+            total: U128(compromisos.values().sum()),
+            compromisos: compromisos
+                .into_iter()
+                .map(|(clave, cuota)| (clave, U128(cuota)))
+                .collect(),
+            limite_global: U128(one_near() * self.limite_exposicion as u128),
+            reputacion: self.reputacion(&cuenta),
+        }
+    }
+
+    // * PERFILES
+
+    // El almacenamiento del perfil lo paga el usuario, el excedente regresa a su saldo.
+    #[payable]
+    pub fn actualizar_perfil(&mut self, cambios: Perfil) -> Perfil {
+        let id_cuenta = env::predecessor_account_id();
+        let mut usuario = self
+            .usuarios
+            .get(&id_cuenta)
+            .unwrap_or_else(|| Usuario::new(String::from(&id_cuenta)));
+
+        let mut perfil = usuario.perfil.clone().unwrap_or_default();
+        perfil.aplicar(cambios);
+        usuario.perfil = Some(perfil.clone());
+
+        let almacenamiento_inicial = env::storage_usage();
+        self.usuarios.insert(&id_cuenta, &usuario);
+        let almacenamiento_final = env::storage_usage();
+
+        let deposito = env::attached_deposit();
+        let reembolso = if almacenamiento_final > almacenamiento_inicial {
+            let costo =
+                (almacenamiento_final - almacenamiento_inicial) as u128 * env::storage_byte_cost();
+            assert!(
+                deposito >= costo,
+                "Se requiere un depósito de {} yoctoNEAR para cubrir el almacenamiento del perfil.",
+                costo
+            );
+            deposito - costo
+        } else {
+            deposito
+                + (almacenamiento_inicial - almacenamiento_final) as u128 * env::storage_byte_cost()
+        };
+
+        if reembolso > 0 {
+            self.acreditar_saldo(
+                &id_cuenta,
+                reembolso,
+                ConceptoMovimiento::Reembolso,
+                String::from("perfil"),
+            );
+        }
+
+        let msg = format!("El usuario {} actualizó su perfil.", &id_cuenta);
+        env::log(msg.as_bytes());
+
+        perfil
+    }
+
+    pub fn consultar_perfil(&self, id_cuenta: Option<String>) -> Option<Perfil> {
+        let cuenta = id_cuenta.unwrap_or(env::predecessor_account_id());
+        self.usuarios
+            .get(&cuenta)
+            .and_then(|usuario| usuario.perfil)
+    }
+
+    pub fn consultar_perfiles_tanda(&self, clave: String) -> HashMap<AccountId, Option<Perfil>> {
+        assert!(self.tandas.get(&clave).is_some(), "La tanda no existe.");
+        let tanda = self.tandas.get(&clave).unwrap();
+
+        tanda
+            .integrantes
+            .iter()
+            .map(|cuenta| {
+                let perfil = self.usuarios.get(cuenta).and_then(|usuario| usuario.perfil);
+                (String::from(cuenta), perfil)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests;
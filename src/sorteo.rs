@@ -0,0 +1,28 @@
+use near_sdk::env;
+use std::convert::TryInto;
+
+// * SORTEO
+// ? Los números se derivan de env::random_seed(), que es el mismo para todo el bloque.
+
+pub fn numero_aleatorio(ronda: u64) -> u64 {
+    let mut semilla = env::random_seed();
+    semilla.extend_from_slice(&ronda.to_le_bytes());
+
+    let hash = env::sha256(&semilla);
+    u64::from_le_bytes(hash[..8].try_into().unwrap())
+}
+
+// Semilla del bloque en hexadecimal, para que cualquiera pueda repetir el sorteo.
+pub fn semilla_hex() -> String {
+    env::random_seed()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub fn mezclar<T>(elementos: &mut [T]) {
+    for i in (1..elementos.len()).rev() {
+        let j = (numero_aleatorio(i as u64) % (i as u64 + 1)) as usize;
+        elementos.swap(i, j);
+    }
+}
@@ -0,0 +1,76 @@
+use super::*;
+use crate::types::ConfiguracionAhorro;
+use crate::TandaDapp;
+use near_sdk::{testing_env, MockedBlockchain};
+
+const INTEGRANTES: [&str; 3] = ["alice", "bob", "carol"];
+
+// Caja de 1 NEAR cada 7 días por 2 periodos, retirarse antes cuesta el 10%.
+fn caja_activa() -> TandaDapp {
+    testing_env!(contexto("org", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_caja_ahorro(
+        "c".into(),
+        3,
+        1,
+        7,
+        ConfiguracionAhorro {
+            num_periodos: 2,
+            meta: 0,
+            penalizacion_retiro: 10,
+        },
+        None,
+    );
+    assert_eq!(c.consultar_periodos(CLAVE.into()).unwrap().len(), 2);
+    for u in INTEGRANTES {
+        testing_env!(contexto(u, 0, T0));
+        c.agregar_integrante(CLAVE.into());
+    }
+    testing_env!(contexto("org", 0, T0));
+    c.activar_tanda(CLAVE.into());
+    c
+}
+
+#[test]
+fn retiro_anticipado_y_liquidacion() {
+    let mut c = caja_activa();
+    pagar(&mut c, &INTEGRANTES, NEAR, T0);
+    assert!(c.consultar_periodos(CLAVE.into()).unwrap()[0].pagos_completos);
+
+    testing_env!(contexto("carol", 0, T0));
+    assert_eq!(c.retirar_ahorro(CLAVE.into()).0, NEAR * 9 / 10);
+
+    pagar(&mut c, &["alice", "bob"], NEAR, T0 + 8 * DIA);
+    assert!(c.consultar_periodos(CLAVE.into()).unwrap()[1].pagos_completos);
+    assert!(!c.consultar_ahorro(CLAVE.into()).liquidable);
+
+    // * La penalización de carol se reparte entre quienes llegaron al vencimiento
+    testing_env!(contexto("alice", 0, T0 + 15 * DIA));
+    let resumen = c.consultar_ahorro(CLAVE.into());
+    assert!(resumen.liquidable);
+    assert_eq!(resumen.fondo_retiros.0, NEAR / 10);
+    c.liquidar_caja_ahorro(CLAVE.into());
+    assert_eq!(
+        c.consultar_saldo(Some("alice".into())).0,
+        2 * NEAR + NEAR / 20
+    );
+    assert_eq!(
+        c.consultar_saldo(Some("bob".into())).0,
+        2 * NEAR + NEAR / 20
+    );
+    assert_eq!(
+        c.consultar_tanda(CLAVE.into()).unwrap().estado,
+        "Finalizada"
+    );
+}
+
+#[test]
+#[should_panic(expected = "se paga sin penalización con liquidar_caja_ahorro")]
+fn caja_vencida_no_admite_retiro() {
+    let mut c = caja_activa();
+    pagar(&mut c, &INTEGRANTES, NEAR, T0);
+    pagar(&mut c, &INTEGRANTES, NEAR, T0 + 8 * DIA);
+
+    testing_env!(contexto("carol", 0, T0 + 15 * DIA));
+    c.retirar_ahorro(CLAVE.into());
+}
@@ -0,0 +1,38 @@
+use super::*;
+use crate::types::{MedidaArbitral, OpcionesTanda};
+use near_sdk::{testing_env, MockedBlockchain};
+
+#[test]
+fn turno_reasignado_paga_al_nuevo_asiento() {
+    let integrantes = ["alice", "bob", "carol"];
+    let mut c = tanda_activa(
+        &integrantes,
+        Some(OpcionesTanda {
+            pago_automatico: Some(true),
+            arbitro: Some("juez".into()),
+            ..Default::default()
+        }),
+    );
+
+    testing_env!(contexto("carol", 0, T0));
+    let id = c.abrir_disputa(CLAVE.into(), 0, "El primer turno era mío".into());
+    pagar(&mut c, &integrantes, NEAR, T0);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 0);
+
+    testing_env!(contexto("juez", 0, T0));
+    c.resolver_disputa(
+        CLAVE.into(),
+        id,
+        "El turno pasa a carol".into(),
+        vec![MedidaArbitral::ReasignarTurno {
+            cuenta: "carol".into(),
+            num_asiento: None,
+        }],
+    );
+
+    let periodo = &c.consultar_periodos(CLAVE.into()).unwrap()[0];
+    assert!(periodo.tanda_pagada);
+    assert_eq!(periodo.asiento_en_turno, Some(3));
+    assert_eq!(c.consultar_saldo(Some("carol".into())).0, 3 * NEAR);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 0);
+}
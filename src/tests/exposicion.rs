@@ -0,0 +1,151 @@
+use super::*;
+use crate::types::{OpcionesTanda, TipoPropuesta};
+use crate::TandaDapp;
+use near_sdk::{testing_env, MockedBlockchain};
+
+fn contexto_bloque(cuenta: &str, bloque: u64) -> near_sdk::VMContext {
+    let mut ctx = contexto(cuenta, 0, T0);
+    ctx.block_index = bloque;
+    ctx
+}
+
+// Límite global de 3 NEAR y tres Tandas mensuales de 1 NEAR que permiten hasta 2, con claves 7, 8 y 9.
+fn tres_tandas() -> TandaDapp {
+    testing_env!(contexto(CONTRATO, 0, T0));
+    let mut c = TandaDapp::default();
+    c.configurar_limite_exposicion(3);
+    for bloque in [7, 8, 9] {
+        let mut ctx = contexto_bloque("org", bloque);
+        ctx.attached_deposit = NEAR;
+        testing_env!(ctx);
+        c.crear_tanda(
+            "t".into(),
+            3,
+            1,
+            30,
+            Some(OpcionesTanda {
+                limite_exposicion: Some(2),
+                ..Default::default()
+            }),
+        );
+    }
+    c
+}
+
+#[test]
+fn dentro_del_limite() {
+    let mut c = tres_tandas();
+    testing_env!(contexto_bloque("alice", 10));
+    c.agregar_integrante("7".into());
+    c.agregar_integrante("8".into());
+
+    let exposicion = c.consultar_exposicion(None);
+    assert_eq!(exposicion.total.0, 2 * NEAR);
+    assert_eq!(exposicion.compromisos.len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "esta Tanda permite")]
+fn excede_el_limite_de_la_tanda() {
+    let mut c = tres_tandas();
+    testing_env!(contexto_bloque("alice", 10));
+    c.agregar_integrante("7".into());
+    c.agregar_integrante("8".into());
+    c.agregar_integrante("9".into());
+}
+
+#[test]
+#[should_panic(expected = "esta Tanda permite")]
+fn varios_asientos_cuentan_completos() {
+    let mut c = tres_tandas();
+    testing_env!(contexto_bloque("alice", 10));
+    c.tomar_asientos("7".into(), 3);
+}
+
+#[test]
+fn cuota_semanal_se_lleva_a_30_dias() {
+    testing_env!(contexto("org", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_tanda("t".into(), 3, 1, 7, None);
+    testing_env!(contexto("alice", 0, T0));
+    c.agregar_integrante(CLAVE.into());
+
+    assert_eq!(c.consultar_exposicion(None).total.0, 30 * NEAR / 7);
+}
+
+#[test]
+#[should_panic(expected = "esta Tanda permite")]
+fn tanda_pausada_sigue_comprometida() {
+    let mut c = tres_tandas();
+    for (i, u) in ["alice", "bob", "carol"].iter().enumerate() {
+        testing_env!(contexto_bloque(u, 10));
+        c.agregar_integrante("7".into());
+        c.escoger_turno("7".into(), i + 1, None);
+    }
+    testing_env!(contexto_bloque("org", 10));
+    c.activar_tanda("7".into());
+    testing_env!(contexto_bloque("alice", 10));
+    let id = c.proponer("7".into(), TipoPropuesta::Pausar);
+    testing_env!(contexto_bloque("bob", 10));
+    c.votar("7".into(), id, true);
+    assert_eq!(c.consultar_tanda("7".into()).unwrap().estado, "Pausada");
+
+    testing_env!(contexto_bloque("alice", 10));
+    c.agregar_integrante("8".into());
+    c.agregar_integrante("9".into());
+}
+
+#[test]
+fn renovacion_deja_fuera_a_quien_excede_el_limite() {
+    testing_env!(contexto("org", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_tanda(
+        "t".into(),
+        3,
+        1,
+        30,
+        Some(OpcionesTanda {
+            pago_automatico: Some(true),
+            renovable: Some(true),
+            limite_exposicion: Some(2),
+            ..Default::default()
+        }),
+    );
+    for bloque in [8, 9] {
+        let mut ctx = contexto_bloque("org", bloque);
+        ctx.attached_deposit = NEAR;
+        testing_env!(ctx);
+        c.crear_tanda("t".into(), 3, 1, 30, None);
+    }
+
+    let integrantes = ["alice", "bob", "carol"];
+    for (i, u) in integrantes.iter().enumerate() {
+        testing_env!(contexto(u, 0, T0));
+        c.agregar_integrante(CLAVE.into());
+        c.escoger_turno(CLAVE.into(), i + 1, None);
+        c.confirmar_renovacion(CLAVE.into(), true);
+    }
+    testing_env!(contexto("org", 0, T0));
+    c.activar_tanda(CLAVE.into());
+
+    // * Durante el ciclo alice se compromete en otras dos Tandas sin límite propio
+    testing_env!(contexto_bloque("alice", 10));
+    c.agregar_integrante("8".into());
+    c.agregar_integrante("9".into());
+
+    for n in 0..3 {
+        pagar(&mut c, &integrantes, NEAR, T0 + n * 30 * DIA);
+    }
+    let tanda = c.consultar_tanda(CLAVE.into()).unwrap();
+    assert_eq!(tanda.estado, "Finalizada");
+
+    // * El nuevo ciclo queda sin alice y pendiente de activar
+    let clave = tanda.ciclo_siguiente.unwrap();
+    let nueva = c.consultar_tanda(String::from(&clave)).unwrap();
+    assert_eq!(nueva.estado, "Pendiente");
+    assert_eq!(nueva.num_integrantes, 2);
+    assert!(!nueva.integrantes.contains("alice"));
+
+    testing_env!(contexto("org", 0, T0 + 90 * DIA));
+    assert!(c.activar_tanda(clave));
+}
@@ -0,0 +1,147 @@
+use super::*;
+use crate::types::{OpcionesTanda, TipoPropuesta};
+use near_sdk::{testing_env, MockedBlockchain};
+
+#[test]
+fn expulsion_y_cambio_de_monto() {
+    let mut c = tanda_activa(&["alice", "bob", "carol"], None);
+    pagar(&mut c, &["alice", "bob"], NEAR, T0);
+
+    testing_env!(contexto("alice", 0, T0 + 10 * DIA));
+    let id = c.proponer(
+        CLAVE.into(),
+        TipoPropuesta::Expulsar {
+            cuenta: "carol".into(),
+        },
+    );
+    assert_eq!(
+        c.consultar_propuestas(CLAVE.into())[id as usize].estado,
+        "Abierta"
+    );
+    testing_env!(contexto("bob", 0, T0 + 10 * DIA));
+    c.votar(CLAVE.into(), id, true);
+    assert_eq!(
+        c.consultar_propuestas(CLAVE.into())[id as usize].estado,
+        "Aprobada"
+    );
+
+    let tanda = c.consultar_tanda(CLAVE.into()).unwrap();
+    assert_eq!(tanda.num_integrantes, 2);
+    assert!(!tanda.integrantes.contains("carol"));
+    let periodos = c.consultar_periodos(CLAVE.into()).unwrap();
+    assert_eq!(periodos.len(), 2);
+    assert!(periodos[0].pagos_completos);
+    assert_eq!(periodos[1].asiento_en_turno, Some(2));
+
+    testing_env!(contexto("alice", 0, T0 + 10 * DIA));
+    let id = c.proponer(CLAVE.into(), TipoPropuesta::CambiarMonto { monto: 3 });
+    testing_env!(contexto("bob", 0, T0 + 10 * DIA));
+    c.votar(CLAVE.into(), id, true);
+    assert_eq!(c.consultar_periodos(CLAVE.into()).unwrap()[1].monto, 3);
+}
+
+#[test]
+fn disolucion_reparte_lo_aportado() {
+    let integrantes = ["alice", "bob", "carol", "dave"];
+    let mut c = tanda_activa(&integrantes, None);
+    pagar(&mut c, &integrantes, NEAR, T0);
+    testing_env!(contexto("org", 0, T0));
+    c.pagar_tanda(CLAVE.into(), 0);
+    pagar(&mut c, &["bob", "carol"], NEAR, T0 + 8 * DIA);
+
+    testing_env!(contexto("bob", 0, T0 + 8 * DIA));
+    let id = c.proponer(CLAVE.into(), TipoPropuesta::Disolver);
+    for u in ["carol", "dave"] {
+        testing_env!(contexto(u, 0, T0 + 8 * DIA));
+        c.votar(CLAVE.into(), id, true);
+    }
+    assert_eq!(c.consultar_tanda(CLAVE.into()).unwrap().estado, "Disuelta");
+
+    // * alice cobró 4 NEAR habiendo aportado 1, los 3 restantes quedan como deuda
+    let deudas = c.consultar_deudas(CLAVE.into());
+    assert_eq!(deudas.len(), 1);
+    assert_eq!(deudas[0].deudor, "alice");
+    assert_eq!(deudas[0].monto.0, 3 * NEAR);
+    let acreedores: u128 = deudas[0].acreedores.values().map(|monto| monto.0).sum();
+    assert_eq!(acreedores, 3 * NEAR);
+    assert_eq!(saldos(&c, &["bob", "carol", "dave"]), 2 * NEAR);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 4 * NEAR);
+}
+
+#[test]
+#[should_panic(expected = "El umbral sólo puede configurarse antes de activar la Tanda.")]
+fn umbral_fijo_despues_de_activar() {
+    let mut c = tanda_activa(&["alice", "bob", "carol"], None);
+    c.configurar_tanda(
+        CLAVE.into(),
+        OpcionesTanda {
+            umbral: Some(0),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn disolucion_vacia_los_monederos() {
+    let mut c = tanda_activa(&["alice", "bob", "carol"], None);
+    testing_env!(contexto("alice", 2 * NEAR, T0));
+    c.depositar_monedero(CLAVE.into());
+    pagar(&mut c, &["bob"], NEAR, T0);
+
+    testing_env!(contexto("alice", 0, T0 + DIA));
+    let id = c.proponer(CLAVE.into(), TipoPropuesta::Disolver);
+    testing_env!(contexto("bob", 0, T0 + DIA));
+    c.votar(CLAVE.into(), id, true);
+
+    // * alice recupera su cuota del periodo sin pagar y lo que quedaba en su monedero
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 2 * NEAR);
+    assert_eq!(
+        c.consultar_monedero(CLAVE.into(), Some("alice".into()))
+            .saldo
+            .0,
+        0
+    );
+}
+
+#[test]
+fn expulsion_reembolsa_lo_aportado_de_mas() {
+    let integrantes = ["alice", "bob", "carol"];
+    let mut c = tanda_activa(
+        &integrantes,
+        Some(OpcionesTanda {
+            pagos_parciales: Some(true),
+            ..Default::default()
+        }),
+    );
+    pagar(&mut c, &integrantes, NEAR, T0);
+    testing_env!(contexto("org", 0, T0));
+    c.pagar_tanda(CLAVE.into(), 0);
+    pagar(&mut c, &["alice", "bob"], NEAR, T0 + 7 * DIA);
+    testing_env!(contexto("carol", NEAR / 2, T0 + 7 * DIA));
+    c.abonar_periodo(CLAVE.into());
+
+    testing_env!(contexto("alice", 0, T0 + 15 * DIA));
+    let id = c.proponer(
+        CLAVE.into(),
+        TipoPropuesta::Expulsar {
+            cuenta: "carol".into(),
+        },
+    );
+    testing_env!(contexto("bob", 0, T0 + 15 * DIA));
+    c.votar(CLAVE.into(), id, true);
+
+    // * carol recupera su abono del periodo sin pagar, la cuota que cobró alice queda como deuda
+    assert_eq!(c.consultar_saldo(Some("carol".into())).0, NEAR / 2);
+    assert_eq!(
+        c.consultar_movimientos(Some("carol".into()), None, None)[0].referencia,
+        "7#2"
+    );
+    let periodo = &c.consultar_periodos(CLAVE.into()).unwrap()[1];
+    assert!(periodo.abonos_parciales.is_empty());
+    assert_eq!(periodo.cantidad_recaudada.0, 2 * NEAR);
+
+    let deudas = c.consultar_deudas(CLAVE.into());
+    assert_eq!(deudas.len(), 1);
+    assert_eq!(deudas[0].deudor, "alice");
+    assert_eq!(deudas[0].acreedores["carol"].0, NEAR);
+}
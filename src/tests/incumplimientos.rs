@@ -0,0 +1,111 @@
+use super::*;
+use crate::types::{OpcionesTanda, TipoPropuesta};
+use crate::TandaDapp;
+use near_sdk::{testing_env, MockedBlockchain};
+
+const INTEGRANTES: [&str; 4] = ["alice", "bob", "carol", "dave"];
+
+fn opciones_automaticas() -> Option<OpcionesTanda> {
+    Some(OpcionesTanda {
+        pago_automatico: Some(true),
+        ..Default::default()
+    })
+}
+
+// alice cobra el primer turno y bob propone expulsarla después de dejar vencer el segundo periodo.
+fn expulsar_alice(c: &mut TandaDapp) {
+    testing_env!(contexto("bob", 0, T0 + 15 * DIA));
+    let id = c.proponer(
+        CLAVE.into(),
+        TipoPropuesta::Expulsar {
+            cuenta: "alice".into(),
+        },
+    );
+    testing_env!(contexto("carol", 0, T0 + 15 * DIA));
+    if c.consultar_propuestas(CLAVE.into())[id as usize].estado != "Aprobada" {
+        c.votar(CLAVE.into(), id, true);
+    }
+}
+
+// La segunda Tanda se crea en otro bloque para que tenga otra clave.
+fn tanda_que_bloquea_morosos(c: &mut TandaDapp) {
+    let mut ctx = contexto("org", NEAR, T0 + 15 * DIA);
+    ctx.block_index = 9;
+    testing_env!(ctx);
+    c.crear_tanda(
+        "u".into(),
+        2,
+        1,
+        7,
+        Some(OpcionesTanda {
+            bloquear_morosos: Some(true),
+            ..Default::default()
+        }),
+    );
+}
+
+fn unirse_a_la_segunda(c: &mut TandaDapp, cuenta: &str, deposito: u128) {
+    let mut ctx = contexto(cuenta, deposito, T0 + 15 * DIA);
+    ctx.block_index = 9;
+    testing_env!(ctx);
+    c.agregar_integrante("9".into());
+}
+
+#[test]
+fn deuda_de_expulsion_se_salda() {
+    let mut c = tanda_activa(&INTEGRANTES, opciones_automaticas());
+    pagar(&mut c, &INTEGRANTES, NEAR, T0);
+    pagar(&mut c, &["bob", "carol", "dave"], NEAR, T0 + 7 * DIA);
+    expulsar_alice(&mut c);
+
+    let deudas = c.consultar_incumplimientos(Some("alice".into()));
+    assert_eq!(deudas.len(), 1);
+    assert_eq!(deudas[0].monto.0, 3 * NEAR);
+
+    tanda_que_bloquea_morosos(&mut c);
+    let mut ctx = contexto("alice", 3 * NEAR, T0 + 15 * DIA);
+    ctx.block_index = 9;
+    testing_env!(ctx);
+    c.saldar_deuda(CLAVE.into());
+    assert!(c.consultar_incumplimientos(None).is_empty());
+    unirse_a_la_segunda(&mut c, "alice", 0);
+    assert_eq!(c.consultar_saldo(Some("dave".into())).0, NEAR);
+}
+
+#[test]
+#[should_panic(expected = "incumplimientos sin resolver")]
+fn deuda_de_expulsion_bloquea_el_ingreso() {
+    let mut c = tanda_activa(&INTEGRANTES, opciones_automaticas());
+    pagar(&mut c, &INTEGRANTES, NEAR, T0);
+    expulsar_alice(&mut c);
+
+    tanda_que_bloquea_morosos(&mut c);
+    unirse_a_la_segunda(&mut c, "alice", 0);
+}
+
+#[test]
+#[should_panic(expected = "incumplimientos sin resolver")]
+fn periodo_vencido_despues_de_cobrar_bloquea_el_ingreso() {
+    let mut c = tanda_activa(&INTEGRANTES, opciones_automaticas());
+    pagar(&mut c, &INTEGRANTES, NEAR, T0);
+    pagar(&mut c, &["bob", "carol", "dave"], NEAR, T0 + 7 * DIA);
+
+    // * Cualquier pago posterior al vencimiento revisa a quienes ya cobraron
+    pagar(&mut c, &["bob"], NEAR, T0 + 15 * DIA);
+    tanda_que_bloquea_morosos(&mut c);
+    unirse_a_la_segunda(&mut c, "alice", 0);
+}
+
+#[test]
+fn ponerse_al_corriente_limpia_el_registro() {
+    let mut c = tanda_activa(&INTEGRANTES, opciones_automaticas());
+    pagar(&mut c, &INTEGRANTES, NEAR, T0);
+    pagar(&mut c, &["bob", "carol", "dave"], NEAR, T0 + 7 * DIA);
+    pagar(&mut c, &["bob", "alice"], NEAR, T0 + 15 * DIA);
+
+    tanda_que_bloquea_morosos(&mut c);
+    unirse_a_la_segunda(&mut c, "alice", 0);
+    assert!(c
+        .consultar_tandas_inscritas(Some("alice".into()))
+        .contains(&"9".to_string()));
+}
@@ -0,0 +1,69 @@
+use crate::types::OpcionesTanda;
+use crate::TandaDapp;
+use near_sdk::json_types::ValidAccountId;
+use near_sdk::test_utils::VMContextBuilder;
+use near_sdk::{testing_env, EpochHeight, MockedBlockchain, VMContext};
+use std::convert::TryFrom;
+
+mod ahorro;
+mod disputas;
+mod exposicion;
+mod gobierno;
+mod incumplimientos;
+mod pagos;
+mod prestamos;
+mod reserva;
+mod staking;
+mod tipos;
+
+pub const NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+pub const DIA: u64 = 86_400_000_000_000;
+pub const T0: u64 = 1_600_000_000_000_000_000;
+pub const CONTRATO: &str = "tanda.near";
+// La clave de las Tandas se genera con el índice del bloque.
+pub const CLAVE: &str = "7";
+
+pub fn contexto(cuenta: &str, deposito: u128, ts: u64) -> VMContext {
+    contexto_epoca(cuenta, deposito, ts, 0)
+}
+
+pub fn contexto_epoca(cuenta: &str, deposito: u128, ts: u64, epoca: EpochHeight) -> VMContext {
+    VMContextBuilder::new()
+        .predecessor_account_id(ValidAccountId::try_from(cuenta).unwrap())
+        .current_account_id(ValidAccountId::try_from(CONTRATO).unwrap())
+        .block_timestamp(ts)
+        .block_index(7)
+        .epoch_height(epoca)
+        .attached_deposit(deposito)
+        .build()
+}
+
+// Tanda de 1 NEAR cada 7 días creada por "org", los turnos siguen el orden de los integrantes.
+pub fn tanda_activa(integrantes: &[&str], opciones: Option<OpcionesTanda>) -> TandaDapp {
+    testing_env!(contexto("org", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_tanda("t".into(), integrantes.len() as u32, 1, 7, opciones);
+    for (i, u) in integrantes.iter().enumerate() {
+        testing_env!(contexto(u, 0, T0));
+        c.agregar_integrante(CLAVE.into());
+        c.escoger_turno(CLAVE.into(), i + 1, None);
+    }
+    testing_env!(contexto("org", 0, T0));
+    c.activar_tanda(CLAVE.into());
+    c
+}
+
+// Cada integrante paga la cuota del periodo vigente en la fecha indicada.
+pub fn pagar(c: &mut TandaDapp, integrantes: &[&str], deposito: u128, ts: u64) {
+    for u in integrantes {
+        testing_env!(contexto(u, deposito, ts));
+        c.agregar_integrante_pago(CLAVE.into());
+    }
+}
+
+pub fn saldos(c: &TandaDapp, integrantes: &[&str]) -> u128 {
+    integrantes
+        .iter()
+        .map(|u| c.consultar_saldo(Some(u.to_string())).0)
+        .sum()
+}
@@ -0,0 +1,187 @@
+use super::*;
+use crate::types::OpcionesTanda;
+use crate::TandaDapp;
+use near_sdk::json_types::U128;
+use near_sdk::{testing_env, MockedBlockchain};
+
+#[test]
+fn pago_automatico_al_completar_el_periodo() {
+    let mut c = tanda_activa(
+        &["bob", "alice"],
+        Some(OpcionesTanda {
+            pago_automatico: Some(true),
+            ..Default::default()
+        }),
+    );
+
+    pagar(&mut c, &["alice", "bob"], NEAR, T0);
+    assert_eq!(c.consultar_saldo(Some("bob".into())).0, 2 * NEAR);
+
+    testing_env!(contexto("bob", 0, T0));
+    c.retirar(None);
+    assert_eq!(c.consultar_saldo(None).0, 0);
+    assert_eq!(c.consultar_movimientos(None, None, None).len(), 2);
+}
+
+#[test]
+fn abonos_parciales_y_pago_multiple() {
+    testing_env!(contexto("org", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_tanda(
+        "t".into(),
+        2,
+        2,
+        7,
+        Some(OpcionesTanda {
+            pagos_parciales: Some(true),
+            ..Default::default()
+        }),
+    );
+    for u in ["alice", "bob"] {
+        testing_env!(contexto(u, 0, T0));
+        c.agregar_integrante(CLAVE.into());
+    }
+
+    testing_env!(contexto("alice", NEAR, T0));
+    assert_eq!(c.abonar_periodo(CLAVE.into()).0, NEAR);
+    assert_eq!(
+        c.consultar_adeudos(CLAVE.into(), Some("alice".into()))[0].0,
+        NEAR
+    );
+    assert_eq!(c.abonar_periodo(CLAVE.into()).0, 0);
+    assert_eq!(c.validar_periodo(CLAVE.into(), Some("alice".into())), 1);
+
+    testing_env!(contexto("bob", 4 * NEAR, T0));
+    c.agregar_integrante_pago_multiple(CLAVE.into(), 2);
+    assert_eq!(c.validar_periodo(CLAVE.into(), Some("bob".into())), -1);
+
+    let periodos = c.consultar_periodos(CLAVE.into()).unwrap();
+    assert!(periodos[0].pagos_completos);
+    assert_eq!(periodos[0].cantidad_recaudada.0, 4 * NEAR);
+}
+
+#[test]
+fn monedero_cubre_el_siguiente_periodo() {
+    let mut c = tanda_activa(
+        &["alice", "bob"],
+        Some(OpcionesTanda {
+            pago_automatico: Some(true),
+            ..Default::default()
+        }),
+    );
+
+    testing_env!(contexto("alice", 2 * NEAR, T0));
+    let monedero = c.depositar_monedero(CLAVE.into());
+    assert_eq!(monedero.saldo.0, NEAR);
+    assert_eq!(monedero.periodos_prepagados, 1);
+    assert_eq!(c.validar_periodo(CLAVE.into(), Some("alice".into())), 1);
+
+    testing_env!(contexto("bob", 0, T0 + 8 * DIA));
+    assert_eq!(c.cobrar_monederos(CLAVE.into()), 1);
+    assert_eq!(c.validar_periodo(CLAVE.into(), Some("alice".into())), -1);
+}
+
+#[test]
+fn pago_en_efectivo_confirmado_por_el_receptor() {
+    let mut c = tanda_activa(
+        &["alice", "bob", "carol"],
+        Some(OpcionesTanda {
+            confirmar_receptor: Some(true),
+            ..Default::default()
+        }),
+    );
+    c.registrar_pago_externo(CLAVE.into(), 0, "carol".into());
+    pagar(&mut c, &["alice", "bob"], NEAR, T0);
+
+    // * El pago cuenta hasta que lo confirman quien pagó y quien lo recibe
+    testing_env!(contexto("carol", 0, T0));
+    c.confirmar_pago_externo(CLAVE.into(), 0, "carol".into(), true);
+    assert!(!c.consultar_periodos(CLAVE.into()).unwrap()[0].pagos_completos);
+    testing_env!(contexto("alice", 0, T0));
+    c.confirmar_pago_externo(CLAVE.into(), 0, "carol".into(), true);
+    assert!(c.consultar_periodos(CLAVE.into()).unwrap()[0].pagos_completos);
+
+    c.pagar_tanda(CLAVE.into(), 0);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 2 * NEAR);
+}
+
+#[test]
+fn traspaso_de_asiento_con_precio() {
+    let mut c = tanda_activa(&["alice", "bob", "carol"], None);
+    pagar(&mut c, &["carol"], NEAR, T0);
+
+    testing_env!(contexto("carol", 0, T0));
+    let id = c.ofrecer_asiento(CLAVE.into(), 3, "dave".into(), Some(U128(NEAR / 2)));
+    testing_env!(contexto("dave", NEAR / 2, T0));
+    c.aceptar_traspaso(CLAVE.into(), id);
+
+    let tanda = c.consultar_tanda(CLAVE.into()).unwrap();
+    assert!(tanda.integrantes.contains("dave") && !tanda.integrantes.contains("carol"));
+    let periodos = c.consultar_periodos(CLAVE.into()).unwrap();
+    assert_eq!(periodos[2].usuario_en_turno, "dave");
+    assert!(periodos[0].integrantes_pagados.contains("dave"));
+    assert_eq!(c.consultar_saldo(Some("carol".into())).0, NEAR / 2);
+    assert!(c
+        .consultar_tandas_inscritas(Some("dave".into()))
+        .contains(&CLAVE.to_string()));
+    assert!(!c
+        .consultar_tandas_inscritas(Some("carol".into()))
+        .contains(&CLAVE.to_string()));
+
+    pagar(&mut c, &["alice", "bob"], NEAR, T0);
+    assert!(c.consultar_periodos(CLAVE.into()).unwrap()[0].pagos_completos);
+    assert_eq!(c.consultar_traspasos(CLAVE.into())[0].estado, "Aceptado");
+}
+
+#[test]
+#[should_panic(expected = "La penalización sólo puede configurarse antes de activar la Tanda.")]
+fn penalizacion_fija_despues_de_activar() {
+    let mut c = tanda_activa(&["alice", "bob"], None);
+    c.configurar_tanda(
+        CLAVE.into(),
+        OpcionesTanda {
+            penalizacion: Some(100),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn asiento_compartido_reparte_el_pago() {
+    testing_env!(contexto("org", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_tanda(
+        "t".into(),
+        2,
+        1,
+        7,
+        Some(OpcionesTanda {
+            pago_automatico: Some(true),
+            ..Default::default()
+        }),
+    );
+    testing_env!(contexto("alice", 0, T0));
+    c.tomar_participacion(CLAVE.into(), None, 33);
+    c.escoger_turno(CLAVE.into(), 1, None);
+    testing_env!(contexto("bob", 0, T0));
+    c.tomar_participacion(CLAVE.into(), Some(1), 67);
+    testing_env!(contexto("carol", 0, T0));
+    c.agregar_integrante(CLAVE.into());
+    c.escoger_turno(CLAVE.into(), 2, None);
+    testing_env!(contexto("org", 0, T0));
+    c.activar_tanda(CLAVE.into());
+
+    pagar(&mut c, &["alice"], NEAR * 33 / 100, T0);
+    pagar(&mut c, &["bob"], NEAR * 67 / 100, T0);
+    pagar(&mut c, &["carol"], NEAR, T0);
+
+    // * Cada titular recibe su porcentaje, el último se queda con el residuo del redondeo
+    assert_eq!(
+        c.consultar_saldo(Some("alice".into())).0,
+        2 * NEAR * 33 / 100
+    );
+    assert_eq!(
+        c.consultar_saldo(Some("bob".into())).0,
+        2 * NEAR - 2 * NEAR * 33 / 100
+    );
+}
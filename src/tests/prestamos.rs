@@ -0,0 +1,109 @@
+use super::*;
+use crate::types::{OpcionesTanda, TipoPropuesta};
+use near_sdk::json_types::U128;
+use near_sdk::{testing_env, MockedBlockchain};
+
+fn opciones_prestamos() -> Option<OpcionesTanda> {
+    Some(OpcionesTanda {
+        pago_automatico: Some(true),
+        prestamos: Some(true),
+        tasa_prestamo: Some(10),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn prestamo_se_descuenta_del_turno() {
+    let integrantes = ["alice", "carol", "bob"];
+    let mut c = tanda_activa(&integrantes, opciones_prestamos());
+    pagar(&mut c, &integrantes, NEAR, T0);
+    pagar(&mut c, &["alice", "bob"], NEAR, T0 + 7 * DIA);
+
+    testing_env!(contexto("carol", 0, T0 + 7 * DIA));
+    assert_eq!(c.limite_prestamo(CLAVE.into(), None).0, 2 * NEAR);
+    c.solicitar_prestamo(CLAVE.into(), U128(NEAR));
+    assert_eq!(c.consultar_saldo(None).0, NEAR);
+
+    // * Al completar su turno el préstamo y el 10% de interés se cobran de lo recibido
+    pagar(&mut c, &["carol"], NEAR, T0 + 7 * DIA);
+    assert_eq!(
+        c.consultar_saldo(Some("carol".into())).0,
+        NEAR + 3 * NEAR - NEAR * 11 / 10
+    );
+    assert_eq!(c.consultar_prestamos(CLAVE.into())[0].estado, "Liquidado");
+
+    pagar(&mut c, &integrantes, NEAR, T0 + 14 * DIA);
+    assert_eq!(
+        c.consultar_tanda(CLAVE.into()).unwrap().estado,
+        "Finalizada"
+    );
+    assert_eq!(saldos(&c, &integrantes), 9 * NEAR);
+}
+
+#[test]
+#[should_panic(expected = "El usuario alice no tiene un turno pendiente de cobro.")]
+fn prestamo_sin_turno_pendiente() {
+    let integrantes = ["alice", "carol", "bob"];
+    let mut c = tanda_activa(&integrantes, opciones_prestamos());
+    pagar(&mut c, &integrantes, NEAR, T0);
+
+    testing_env!(contexto("alice", 0, T0));
+    c.solicitar_prestamo(CLAVE.into(), U128(NEAR / 2));
+}
+
+#[test]
+fn disolucion_con_prestamo_vigente() {
+    let integrantes = ["alice", "carol", "bob"];
+    let mut c = tanda_activa(&integrantes, opciones_prestamos());
+    pagar(&mut c, &["alice", "bob"], NEAR, T0);
+
+    // * El límite es el turno pendiente menos el interés que generaría
+    testing_env!(contexto("carol", 0, T0));
+    assert_eq!(
+        c.limite_prestamo(CLAVE.into(), None).0,
+        3 * NEAR * 100 / 110
+    );
+    c.solicitar_prestamo(CLAVE.into(), U128(NEAR));
+
+    testing_env!(contexto("alice", 0, T0 + DIA));
+    let id = c.proponer(CLAVE.into(), TipoPropuesta::Disolver);
+    testing_env!(contexto("bob", 0, T0 + DIA));
+    c.votar(CLAVE.into(), id, true);
+    assert_eq!(c.consultar_tanda(CLAVE.into()).unwrap().estado, "Disuelta");
+
+    // * carol ya tiene 1 NEAR prestado, lo que queda en la Tanda se reparte y el resto es deuda
+    assert_eq!(saldos(&c, &integrantes), 2 * NEAR);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, NEAR / 2);
+    assert_eq!(
+        c.consultar_prestamos(CLAVE.into())[0].estado,
+        "Convertido en deuda"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "La tasa de interés de los préstamos sólo puede configurarse antes de activar la Tanda."
+)]
+fn tasa_fija_despues_de_activar() {
+    let mut c = tanda_activa(&["alice", "carol", "bob"], opciones_prestamos());
+    c.configurar_tanda(
+        CLAVE.into(),
+        OpcionesTanda {
+            tasa_prestamo: Some(100),
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "Sólo carol puede abonar al préstamo 1.")]
+fn solo_el_deudor_abona() {
+    let integrantes = ["alice", "carol", "bob"];
+    let mut c = tanda_activa(&integrantes, opciones_prestamos());
+    pagar(&mut c, &["alice", "bob"], NEAR, T0);
+    testing_env!(contexto("carol", 0, T0));
+    let id = c.solicitar_prestamo(CLAVE.into(), U128(NEAR));
+
+    testing_env!(contexto("bob", NEAR, T0));
+    c.pagar_prestamo(CLAVE.into(), id);
+}
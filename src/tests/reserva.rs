@@ -0,0 +1,73 @@
+use super::*;
+use crate::types::OpcionesTanda;
+use near_sdk::{testing_env, MockedBlockchain};
+
+fn opciones_reserva() -> Option<OpcionesTanda> {
+    Some(OpcionesTanda {
+        pago_automatico: Some(true),
+        reserva: Some(50),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn reserva_cubre_el_faltante() {
+    let integrantes = ["alice", "bob", "carol"];
+    let mut c = tanda_activa(&integrantes, opciones_reserva());
+    pagar(&mut c, &integrantes, NEAR * 3 / 2, T0);
+    assert_eq!(c.consultar_reserva(CLAVE.into()).saldo.0, NEAR * 3 / 2);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 3 * NEAR);
+
+    pagar(&mut c, &["alice", "bob"], NEAR * 3 / 2, T0 + 7 * DIA);
+    testing_env!(contexto("org", 0, T0 + 14 * DIA));
+    assert!(c.cubrir_con_reserva(CLAVE.into(), 1));
+    assert_eq!(c.consultar_saldo(Some("bob".into())).0, 3 * NEAR);
+    assert_eq!(c.consultar_reserva(CLAVE.into()).saldo.0, NEAR * 3 / 2);
+
+    testing_env!(contexto("carol", NEAR, T0 + 14 * DIA));
+    assert_eq!(c.reembolsar_reserva(CLAVE.into()).0, 0);
+    pagar(&mut c, &integrantes, NEAR * 3 / 2, T0 + 14 * DIA);
+    assert_eq!(
+        c.consultar_tanda(CLAVE.into()).unwrap().estado,
+        "Finalizada"
+    );
+    assert_eq!(c.consultar_reserva(CLAVE.into()).saldo.0, 0);
+    assert_eq!(saldos(&c, &integrantes), 13 * NEAR);
+}
+
+#[test]
+fn reserva_no_cubre_a_quien_paga_en_efectivo() {
+    let integrantes = ["alice", "bob", "carol"];
+    let mut c = tanda_activa(&integrantes, opciones_reserva());
+    c.registrar_pago_externo(CLAVE.into(), 0, "carol".into());
+    testing_env!(contexto("carol", 0, T0));
+    c.confirmar_pago_externo(CLAVE.into(), 0, "carol".into(), true);
+    pagar(&mut c, &["alice", "bob"], NEAR * 3 / 2, T0);
+    assert_eq!(c.consultar_reserva(CLAVE.into()).saldo.0, NEAR);
+    assert_eq!(c.consultar_reserva(CLAVE.into()).aportaciones.len(), 2);
+
+    // * bob también falta, la reserva lo cubre a él pero no a carol
+    pagar(&mut c, &["alice"], NEAR * 3 / 2, T0 + 7 * DIA);
+    testing_env!(contexto("org", 0, T0 + 14 * DIA));
+    assert!(c.cubrir_con_reserva(CLAVE.into(), 1));
+    let periodo = &c.consultar_periodos(CLAVE.into()).unwrap()[1];
+    assert!(periodo.integrantes_pagados.contains("bob"));
+    assert!(!periodo.integrantes_pagados.contains("carol"));
+    assert!(!periodo.pagos_completos);
+    assert_eq!(c.consultar_reserva(CLAVE.into()).saldo.0, NEAR / 2);
+}
+
+#[test]
+#[should_panic(
+    expected = "El porcentaje de reserva sólo puede configurarse antes de activar la Tanda."
+)]
+fn reserva_fija_despues_de_activar() {
+    let mut c = tanda_activa(&["alice", "bob", "carol"], opciones_reserva());
+    c.configurar_tanda(
+        CLAVE.into(),
+        OpcionesTanda {
+            reserva: Some(100),
+            ..Default::default()
+        },
+    );
+}
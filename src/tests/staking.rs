@@ -0,0 +1,334 @@
+use super::*;
+use crate::types::OpcionesTanda;
+use crate::TandaDapp;
+use mock_pool::{MockPool, EPOCAS_DESDELEGACION};
+use near_sdk::json_types::U128;
+use near_sdk::serde::Deserialize;
+use near_sdk::serde_json::{self, Value};
+use near_sdk::test_utils::get_created_receipts;
+use near_sdk::{testing_env, Gas, MockedBlockchain, PromiseResult};
+use std::collections::VecDeque;
+
+const POOL: &str = "pool.near";
+
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct Recibo {
+    receiver_id: String,
+    actions: Vec<Accion>,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+enum Accion {
+    FunctionCall(Llamada),
+    Transfer(near_sdk::serde::de::IgnoredAny),
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct Llamada {
+    method_name: String,
+    args: String,
+    gas: Gas,
+    deposit: u128,
+}
+
+// Llamadas a contratos que dejó programadas la última ejecución, en orden.
+fn llamadas() -> Vec<(String, Llamada)> {
+    let recibos: Vec<Recibo> =
+        serde_json::from_str(&serde_json::to_string(&get_created_receipts()).unwrap()).unwrap();
+
+    recibos
+        .into_iter()
+        .flat_map(|recibo| {
+            let receptor = recibo.receiver_id;
+            recibo
+                .actions
+                .into_iter()
+                .filter_map(move |accion| match accion {
+                    Accion::FunctionCall(llamada) => Some((receptor.clone(), llamada)),
+                    Accion::Transfer(_) => None,
+                })
+        })
+        .collect()
+}
+
+fn monto(args: &Value, campo: &str) -> U128 {
+    U128(args[campo].as_str().unwrap().parse().unwrap())
+}
+
+// Ejecuta las promesas pendientes contra el pool simulado, cada callback recibe el resultado
+// de la llamada anterior con el gas que se le asignó.
+fn ejecutar_promesas(c: &mut TandaDapp, pool: &mut MockPool, ts: u64, epoca: u64) {
+    let mut pendientes: VecDeque<(String, Llamada)> = llamadas().into();
+    let mut resultado: Vec<u8> = vec![];
+
+    while let Some((receptor, llamada)) = pendientes.pop_front() {
+        let args: Value = serde_json::from_str(&llamada.args).unwrap_or(Value::Null);
+
+        if receptor == POOL {
+            let mut ctx = contexto_epoca(CONTRATO, llamada.deposit, ts, epoca);
+            ctx.current_account_id = POOL.to_string();
+            testing_env!(ctx);
+
+            resultado = match llamada.method_name.as_str() {
+                "get_account_staked_balance" => serde_json::to_vec(
+                    &pool.get_account_staked_balance(args["account_id"].as_str().unwrap().into()),
+                )
+                .unwrap(),
+                "deposit_and_stake" => {
+                    pool.deposit_and_stake();
+                    vec![]
+                }
+                "unstake" => {
+                    pool.unstake(monto(&args, "amount"));
+                    vec![]
+                }
+                "withdraw" => {
+                    pool.withdraw(monto(&args, "amount"));
+                    vec![]
+                }
+                metodo => panic!("Método del pool no simulado: {}", metodo),
+            };
+        } else {
+            let mut ctx = contexto_epoca(CONTRATO, 0, ts, epoca);
+            ctx.prepaid_gas = llamada.gas;
+            testing_env!(
+                ctx,
+                Default::default(),
+                Default::default(),
+                Default::default(),
+                vec![PromiseResult::Successful(resultado.clone())]
+            );
+
+            let clave = args["clave"].as_str().unwrap().to_string();
+            match llamada.method_name.as_str() {
+                "resolver_consulta_delegacion" => {
+                    c.resolver_consulta_delegacion(clave, monto(&args, "monto"));
+                }
+                "resolver_delegacion" => {
+                    c.resolver_delegacion(clave, monto(&args, "monto"), monto(&args, "acciones"));
+                }
+                "resolver_consulta_desdelegacion" => {
+                    let monto = serde_json::from_value(args["monto"].clone()).unwrap();
+                    c.resolver_consulta_desdelegacion(clave, monto);
+                }
+                "resolver_desdelegacion" => {
+                    let desdelegacion =
+                        serde_json::from_value(args["desdelegacion"].clone()).unwrap();
+                    c.resolver_desdelegacion(clave, desdelegacion);
+                }
+                "resolver_retiro_staking" => {
+                    c.resolver_retiro_staking(
+                        clave,
+                        monto(&args, "principal"),
+                        monto(&args, "rendimiento"),
+                    );
+                }
+                metodo => panic!("Callback no simulado: {}", metodo),
+            }
+        }
+
+        for nueva in llamadas().into_iter().rev() {
+            pendientes.push_front(nueva);
+        }
+    }
+}
+
+fn opciones_staking() -> OpcionesTanda {
+    OpcionesTanda {
+        pago_automatico: Some(true),
+        pool_staking: Some(POOL.to_string()),
+        ..Default::default()
+    }
+}
+
+// Tanda de 1 NEAR por periodo de 10 días, activa en T0 con los turnos en el orden indicado.
+fn tanda_con_pool(integrantes: &[&str]) -> TandaDapp {
+    testing_env!(contexto(CONTRATO, 0, T0));
+    let mut c = TandaDapp::default();
+    c.agregar_pool_staking(POOL.to_string());
+
+    testing_env!(contexto("alice", NEAR, T0));
+    c.crear_tanda(
+        "t".into(),
+        integrantes.len() as u32,
+        1,
+        10,
+        Some(opciones_staking()),
+    );
+    for (i, u) in integrantes.iter().enumerate() {
+        testing_env!(contexto(u, 0, T0));
+        c.agregar_integrante(CLAVE.into());
+        c.escoger_turno(CLAVE.into(), i + 1, None);
+    }
+    testing_env!(contexto("alice", 0, T0));
+    c.activar_tanda(CLAVE.into());
+    c
+}
+
+#[test]
+fn ciclo_completo_con_staking() {
+    let mut c = tanda_con_pool(&["alice", "bob"]);
+    let mut pool = MockPool::default();
+
+    // * Lo aportado al periodo 1 se delega mientras falta la cuota de bob
+    testing_env!(contexto("alice", NEAR, T0));
+    c.agregar_integrante_pago(CLAVE.into());
+    c.delegar_fondos(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0, 1);
+
+    let staking = c.consultar_staking(CLAVE.into());
+    assert_eq!(staking.depositado.0, NEAR);
+    assert_eq!(staking.acciones.0, NEAR);
+    assert_eq!(pool.get_account_staked_balance(CONTRATO.into()).0, NEAR);
+
+    testing_env!(contexto_epoca(CONTRATO, 0, T0, 2));
+    pool.agregar_recompensa(U128(NEAR / 10));
+
+    // * Al completarse el periodo sus fondos dejan el pool sin que nadie lo pida
+    testing_env!(contexto_epoca("bob", NEAR, T0 + 8 * DIA, 2));
+    c.agregar_integrante_pago(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0 + 8 * DIA, 2);
+
+    let staking = c.consultar_staking(CLAVE.into());
+    assert_eq!(staking.acciones.0, 0);
+    assert_eq!(staking.depositado.0, 0);
+    assert_eq!(staking.principal_por_retirar.0, NEAR);
+    assert_eq!(staking.rendimiento_por_retirar.0, NEAR / 10);
+    assert!(!c.consultar_periodos(CLAVE.into()).unwrap()[0].tanda_pagada);
+
+    // * Pasada la espera del pool, el siguiente pago retira los fondos y paga el periodo
+    let epoca = 2 + EPOCAS_DESDELEGACION;
+    testing_env!(contexto_epoca("alice", NEAR, T0 + 10 * DIA, epoca));
+    c.agregar_integrante_pago(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0 + 10 * DIA, epoca);
+
+    let staking = c.consultar_staking(CLAVE.into());
+    assert_eq!(staking.inmovilizado(), 0);
+    assert_eq!(staking.rendimiento_por_retirar.0, 0);
+    assert!(c.consultar_periodos(CLAVE.into()).unwrap()[0].tanda_pagada);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 2 * NEAR);
+
+    // * Al finalizar, el rendimiento se reparte según la participación
+    testing_env!(contexto_epoca("bob", NEAR, T0 + 10 * DIA, epoca));
+    c.agregar_integrante_pago(CLAVE.into());
+    assert_eq!(
+        c.consultar_tanda(CLAVE.into()).unwrap().estado,
+        "Finalizada"
+    );
+    assert!(c.consultar_staking(CLAVE.into()).rendimiento_repartido);
+    assert_eq!(
+        c.consultar_saldo(Some("alice".into())).0,
+        2 * NEAR + NEAR / 20
+    );
+    assert_eq!(
+        c.consultar_saldo(Some("bob".into())).0,
+        2 * NEAR + NEAR / 20
+    );
+}
+
+#[test]
+fn desdelegacion_parcial() {
+    let mut c = tanda_con_pool(&["alice", "bob", "carol"]);
+    let mut pool = MockPool::default();
+
+    for u in ["alice", "bob"] {
+        testing_env!(contexto(u, NEAR, T0));
+        c.agregar_integrante_pago(CLAVE.into());
+    }
+    testing_env!(contexto("alice", NEAR, T0 + DIA));
+    c.agregar_integrante_pago(CLAVE.into());
+    c.delegar_fondos(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0 + DIA, 1);
+    assert_eq!(c.consultar_staking(CLAVE.into()).depositado.0, 3 * NEAR);
+
+    testing_env!(contexto_epoca(CONTRATO, 0, T0 + DIA, 2));
+    pool.agregar_recompensa(U128(3 * NEAR / 10));
+
+    // * Sólo sale del pool lo que falta para pagar el periodo 1, el periodo 2 sigue delegado
+    testing_env!(contexto_epoca("carol", NEAR, T0 + 8 * DIA, 2));
+    c.agregar_integrante_pago(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0 + 8 * DIA, 2);
+
+    let staking = c.consultar_staking(CLAVE.into());
+    assert!(staking.principal_por_retirar.0 >= 2 * NEAR);
+    assert_eq!(
+        staking.depositado.0 + staking.principal_por_retirar.0,
+        3 * NEAR
+    );
+    assert!(staking.rendimiento_por_retirar.0 >= NEAR / 5);
+    assert!(staking.rendimiento_por_retirar.0 <= NEAR / 5 + 1);
+
+    let epoca = 2 + EPOCAS_DESDELEGACION;
+    testing_env!(contexto_epoca("bob", NEAR, T0 + 9 * DIA, epoca));
+    c.agregar_integrante_pago(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0 + 9 * DIA, epoca);
+
+    assert!(c.consultar_periodos(CLAVE.into()).unwrap()[0].tanda_pagada);
+    assert_eq!(c.consultar_saldo(Some("alice".into())).0, 3 * NEAR);
+    assert!(c.consultar_staking(CLAVE.into()).depositado.0 > 0);
+}
+
+#[test]
+#[should_panic(expected = "No hay fondos ociosos para delegar.")]
+fn periodo_por_vencer_no_se_delega() {
+    let mut c = tanda_con_pool(&["alice", "bob"]);
+
+    testing_env!(contexto("alice", NEAR, T0 + 7 * DIA));
+    c.agregar_integrante_pago(CLAVE.into());
+    c.delegar_fondos(CLAVE.into());
+}
+
+#[test]
+fn sin_gas_los_fondos_siguen_delegados() {
+    let mut c = tanda_con_pool(&["alice", "bob"]);
+    let mut pool = MockPool::default();
+
+    testing_env!(contexto("alice", NEAR, T0));
+    c.agregar_integrante_pago(CLAVE.into());
+    c.delegar_fondos(CLAVE.into());
+    ejecutar_promesas(&mut c, &mut pool, T0, 1);
+
+    let mut ctx = contexto_epoca("bob", NEAR, T0 + DIA, 1);
+    ctx.prepaid_gas = 30_000_000_000_000;
+    testing_env!(ctx);
+    c.agregar_integrante_pago(CLAVE.into());
+
+    assert!(llamadas().is_empty());
+    assert_eq!(c.consultar_staking(CLAVE.into()).depositado.0, NEAR);
+    assert!(!c.consultar_periodos(CLAVE.into()).unwrap()[0].tanda_pagada);
+}
+
+#[test]
+#[should_panic(expected = "El pool otro-pool.near no está habilitado para staking.")]
+fn pool_no_habilitado() {
+    testing_env!(contexto("alice", NEAR, T0));
+    let mut c = TandaDapp::default();
+    c.crear_tanda(
+        "t".into(),
+        2,
+        1,
+        10,
+        Some(OpcionesTanda {
+            pool_staking: Some("otro-pool.near".into()),
+            ..Default::default()
+        }),
+    );
+}
+
+#[test]
+#[should_panic(expected = "El pool de staking no puede cambiarse después de activar la Tanda.")]
+fn pool_fijo_despues_de_activar() {
+    let mut c = tanda_con_pool(&["alice", "bob"]);
+
+    testing_env!(contexto("alice", 0, T0));
+    c.configurar_tanda(
+        CLAVE.into(),
+        OpcionesTanda {
+            pool_staking: Some(String::new()),
+            ..Default::default()
+        },
+    );
+}
@@ -0,0 +1,86 @@
+use super::*;
+use crate::types::{Perfil, Prestamo, Propuesta, TipoPropuesta, DIAS_VOTACION};
+use near_sdk::{testing_env, MockedBlockchain};
+
+fn propuesta(favor: &[&str], contra: &[&str]) -> Propuesta {
+    let mut propuesta = Propuesta::new(0, TipoPropuesta::Pausar, 50, 50);
+    propuesta.votos_favor = favor.iter().map(|cuenta| cuenta.to_string()).collect();
+    propuesta.votos_contra = contra.iter().map(|cuenta| cuenta.to_string()).collect();
+    propuesta
+}
+
+#[test]
+fn propuesta_aprobada_antes_del_cierre() {
+    testing_env!(contexto("alice", 0, T0));
+
+    // * Con 5 votantes hacen falta 3 votos a favor, aunque el resto no haya votado
+    assert!(!propuesta(&["a", "b"], &[]).aprobada(5));
+    assert!(propuesta(&["a", "b", "c"], &[]).aprobada(5));
+    assert!(!propuesta(&["a", "b"], &["c", "d"]).aprobada(4));
+}
+
+#[test]
+fn propuesta_rechazada_antes_del_cierre() {
+    testing_env!(contexto("alice", 0, T0));
+
+    assert!(!propuesta(&[], &["a"]).rechazada(4));
+    assert!(propuesta(&[], &["a", "b"]).rechazada(4));
+    assert!(propuesta(&["a", "b"], &["c", "d"]).rechazada(4));
+    assert!(!propuesta(&["a", "b"], &["c"]).rechazada(5));
+}
+
+#[test]
+fn propuesta_vencida_cuenta_los_votos_emitidos() {
+    testing_env!(contexto("alice", 0, T0));
+    let abierta = propuesta(&["a", "b"], &["c"]);
+    assert!(!abierta.aprobada(5) && !abierta.rechazada(5));
+    let sin_quorum = propuesta(&["a"], &[]);
+
+    testing_env!(contexto("alice", 0, T0 + (DIAS_VOTACION as u64 + 1) * DIA));
+    assert!(abierta.aprobada(5));
+    assert!(!sin_quorum.aprobada(5) && sin_quorum.rechazada(5));
+}
+
+#[test]
+fn abonos_cubren_primero_los_intereses() {
+    testing_env!(contexto("alice", 0, T0));
+    let mut prestamo = Prestamo::new(1, "alice".into(), 10 * NEAR, NEAR, 30);
+
+    assert_eq!(prestamo.abonar(NEAR / 2), NEAR / 2);
+    assert_eq!(prestamo.capital_pendiente(), 10 * NEAR);
+    assert_eq!(prestamo.abonar(NEAR), NEAR / 2);
+    assert_eq!(prestamo.capital_pendiente(), 10 * NEAR - NEAR / 2);
+    assert_eq!(prestamo.estado, "Vigente");
+
+    assert_eq!(prestamo.abonar(10 * NEAR - NEAR / 2), 0);
+    assert_eq!(prestamo.pendiente(), 0);
+    assert_eq!(prestamo.estado, "Liquidado");
+}
+
+#[test]
+fn perfil_solo_cambia_los_campos_indicados() {
+    let mut perfil = Perfil {
+        nombre: Some("Alicia".into()),
+        idioma: Some("es".into()),
+        ..Default::default()
+    };
+
+    perfil.aplicar(Perfil {
+        idioma: Some("".into()),
+        contacto: Some("alicia@correo.mx".into()),
+        ..Default::default()
+    });
+    assert_eq!(perfil.nombre.as_deref(), Some("Alicia"));
+    assert!(perfil.idioma.is_none());
+    assert_eq!(perfil.contacto.as_deref(), Some("alicia@correo.mx"));
+}
+
+#[test]
+#[should_panic(expected = "Los campos del perfil no pueden exceder 256 caracteres.")]
+fn perfil_con_campo_demasiado_largo() {
+    let mut perfil = Perfil::default();
+    perfil.aplicar(Perfil {
+        nombre: Some("a".repeat(257)),
+        ..Default::default()
+    });
+}
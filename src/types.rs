@@ -1,12 +1,28 @@
 use crate::date_handling;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{env, AccountId};
-use std::collections::HashSet;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 
+// Las participaciones en un asiento se expresan en porcentaje.
+pub const PARTICIPACION_COMPLETA: u32 = 100;
+
+pub const QUORUM_PREDETERMINADO: u32 = 50;
+pub const UMBRAL_PREDETERMINADO: u32 = 50;
+// Al cierre de la votación se decide sólo con los votos emitidos.
+pub const DIAS_VOTACION: i64 = 7;
+
+// Una disputa sin resolver deja de congelar los pagos al vencer.
+pub const DIAS_DISPUTA: i64 = 14;
+pub const MAX_DISPUTAS_INTEGRANTE: usize = 3;
+
+pub const TOKEN_NEAR: &str = "NEAR";
+
 // * TANDA
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Tanda {
     pub id: String,
@@ -20,11 +36,45 @@ pub struct Tanda {
     pub periodo: u32,
     pub estado: String,
     pub integrantes: HashSet<AccountId>,
+    pub asientos: Vec<Asiento>,
+    pub pago_automatico: bool,
+    pub pagos_parciales: bool,
+    pub renovable: bool,
+    pub orden_renovacion: OrdenTurnos,
+    pub renovaciones: HashSet<AccountId>,
+    pub ciclo: u32,
+    pub ciclo_anterior: Option<String>,
+    pub ciclo_siguiente: Option<String>,
+    pub pausada: bool,
+    pub quorum: u32,
+    pub umbral: u32,
+    pub organizadores: HashMap<AccountId, RolOrganizador>,
+    pub aprobaciones: u32,
+    pub arbitro: Option<AccountId>,
+    pub confirmar_receptor: bool,
+    pub penalizacion: u32,
+    pub pool_staking: Option<AccountId>,
+    pub ahorro: Option<CajaAhorro>,
+    pub asignacion_turnos: AsignacionTurnos,
+    pub prestamos: bool,
+    pub tasa_prestamo: u32,
+    pub plazo_prestamo: u32,
+    pub reserva: u32,
+    pub bloquear_morosos: bool,
+    pub atestaciones_requeridas: Vec<String>,
+    pub limite_exposicion: u32,
+    pub escalar_por_reputacion: bool,
 }
 
 impl Tanda {
-    pub fn new(nombre: String, num_integrantes: u32, monto: u32, periodo: u32) -> Self {
-        Self {
+    pub fn new(
+        nombre: String,
+        num_integrantes: u32,
+        monto: u32,
+        periodo: u32,
+        opciones: OpcionesTanda,
+    ) -> Self {
+        let mut tanda = Self {
             id: env::block_index().to_string(),
             creador: env::predecessor_account_id(),
             nombre_tanda: String::from(&nombre),
@@ -39,44 +89,556 @@ impl Tanda {
             periodo,
             estado: String::from("Pendiente"),
             integrantes: HashSet::new(),
-        }
+            asientos: Vec::new(),
+            pago_automatico: false,
+            pagos_parciales: false,
+            renovable: false,
+            orden_renovacion: OrdenTurnos::Rotar,
+            renovaciones: HashSet::new(),
+            ciclo: 1,
+            ciclo_anterior: None,
+            ciclo_siguiente: None,
+            pausada: false,
+            quorum: QUORUM_PREDETERMINADO,
+            umbral: UMBRAL_PREDETERMINADO,
+            organizadores: HashMap::new(),
+            aprobaciones: 1,
+            arbitro: None,
+            confirmar_receptor: false,
+            penalizacion: 0,
+            pool_staking: None,
+            ahorro: None,
+            asignacion_turnos: AsignacionTurnos::Eleccion,
+            prestamos: false,
+            tasa_prestamo: 0,
+            plazo_prestamo: periodo,
+            reserva: 0,
+            bloquear_morosos: false,
+            atestaciones_requeridas: Vec::new(),
+            limite_exposicion: 0,
+            escalar_por_reputacion: false,
+        };
+
+        tanda.aplicar_opciones(opciones);
+        tanda
     }
 
-    pub fn agregar_integrante(&mut self, integrante: AccountId) {
-        if u32::try_from(self.integrantes.len()).unwrap() < self.num_integrantes {
-            self.integrantes.insert(integrante);
-            env::log(
-                format!(
-                    "Integrante nuevo {} agregado exitosamente.",
-                    env::predecessor_account_id()
-                )
-                .as_bytes(),
+    pub fn aplicar_opciones(&mut self, opciones: OpcionesTanda) {
+        if let Some(pago_automatico) = opciones.pago_automatico {
+            self.pago_automatico = pago_automatico;
+        }
+
+        if let Some(pagos_parciales) = opciones.pagos_parciales {
+            assert!(
+                !self.activa,
+                "Los pagos parciales sólo pueden configurarse antes de activar la Tanda."
             );
-        } else {
-            env::log(
-                format!("La Tanda se encuentra llena, ya no existen lugares disponibles.")
-                    .as_bytes(),
+            self.pagos_parciales = pagos_parciales;
+        }
+
+        if let Some(asignacion_turnos) = opciones.asignacion_turnos {
+            assert!(
+                !self.activa,
+                "La asignación de turnos sólo puede configurarse antes de activar la Tanda."
+            );
+            self.asignacion_turnos = asignacion_turnos;
+        }
+
+        if let Some(renovable) = opciones.renovable {
+            self.renovable = renovable;
+        }
+
+        if let Some(orden_renovacion) = opciones.orden_renovacion {
+            self.orden_renovacion = orden_renovacion;
+        }
+
+        // * Las reglas de votación no cambian sin votar una vez que la Tanda está activa
+        if let Some(quorum) = opciones.quorum {
+            assert!(
+                !self.activa,
+                "El quórum sólo puede configurarse antes de activar la Tanda."
+            );
+            assert!(
+                quorum > 0 && quorum <= 100,
+                "El quórum debe estar entre 1 y 100."
+            );
+            self.quorum = quorum;
+        }
+
+        if let Some(umbral) = opciones.umbral {
+            assert!(
+                !self.activa,
+                "El umbral sólo puede configurarse antes de activar la Tanda."
+            );
+            assert!(umbral < 100, "El umbral debe ser menor a 100.");
+            self.umbral = umbral;
+        }
+
+        if let Some(aprobaciones) = opciones.aprobaciones {
+            assert!(
+                aprobaciones > 0 && aprobaciones <= self.num_administradores(),
+                "Las aprobaciones requeridas deben estar entre 1 y {}.",
+                self.num_administradores()
+            );
+            self.aprobaciones = aprobaciones;
+        }
+
+        if let Some(arbitro) = opciones.arbitro {
+            assert!(
+                self.arbitro.is_none(),
+                "El árbitro ya fue elegido, sólo el dueño del contrato puede cambiarlo."
+            );
+            self.validar_arbitro(&arbitro);
+            self.arbitro = Some(arbitro);
+        }
+
+        if let Some(confirmar_receptor) = opciones.confirmar_receptor {
+            self.confirmar_receptor = confirmar_receptor;
+        }
+
+        if let Some(penalizacion) = opciones.penalizacion {
+            assert!(
+                !self.activa,
+                "La penalización sólo puede configurarse antes de activar la Tanda."
+            );
+            assert!(
+                penalizacion <= 100,
+                "La penalización debe estar entre 0 y 100."
+            );
+            self.penalizacion = penalizacion;
+        }
+
+        if let Some(bloquear_morosos) = opciones.bloquear_morosos {
+            self.bloquear_morosos = bloquear_morosos;
+        }
+
+        if let Some(atestaciones_requeridas) = opciones.atestaciones_requeridas {
+            self.atestaciones_requeridas = atestaciones_requeridas;
+        }
+
+        if let Some(limite_exposicion) = opciones.limite_exposicion {
+            self.limite_exposicion = limite_exposicion;
+        }
+
+        if let Some(escalar_por_reputacion) = opciones.escalar_por_reputacion {
+            self.escalar_por_reputacion = escalar_por_reputacion;
+        }
+
+        if let Some(reserva) = opciones.reserva {
+            assert!(
+                !self.activa,
+                "El porcentaje de reserva sólo puede configurarse antes de activar la Tanda."
+            );
+            assert!(
+                reserva <= 100,
+                "El porcentaje de reserva debe estar entre 0 y 100."
+            );
+            self.reserva = reserva;
+        }
+
+        if let Some(prestamos) = opciones.prestamos {
+            self.prestamos = prestamos;
+        }
+
+        if let Some(tasa_prestamo) = opciones.tasa_prestamo {
+            assert!(
+                !self.activa,
+                "La tasa de interés de los préstamos sólo puede configurarse antes de activar la Tanda."
+            );
+            assert!(
+                tasa_prestamo <= 100,
+                "La tasa de interés de los préstamos debe estar entre 0 y 100."
             );
+            self.tasa_prestamo = tasa_prestamo;
+        }
+
+        if let Some(plazo_prestamo) = opciones.plazo_prestamo {
+            assert!(
+                !self.activa,
+                "El plazo de los préstamos sólo puede configurarse antes de activar la Tanda."
+            );
+            assert!(
+                plazo_prestamo > 0,
+                "El plazo de los préstamos no puede ser menor a 1 día."
+            );
+            self.plazo_prestamo = plazo_prestamo;
+        }
+
+        if let Some(pool_staking) = opciones.pool_staking {
+            self.pool_staking = if pool_staking.is_empty() {
+                None
+            } else {
+                Some(pool_staking)
+            };
+        }
+    }
+
+    pub fn opciones(&self) -> OpcionesTanda {
+        OpcionesTanda {
+            pago_automatico: Some(self.pago_automatico),
+            pagos_parciales: Some(self.pagos_parciales),
+            asignacion_turnos: Some(self.asignacion_turnos.clone()),
+            renovable: Some(self.renovable),
+            orden_renovacion: Some(self.orden_renovacion.clone()),
+            quorum: Some(self.quorum),
+            umbral: Some(self.umbral),
+            aprobaciones: Some(self.aprobaciones),
+            arbitro: None,
+            confirmar_receptor: Some(self.confirmar_receptor),
+            penalizacion: Some(self.penalizacion),
+            bloquear_morosos: Some(self.bloquear_morosos),
+            atestaciones_requeridas: Some(self.atestaciones_requeridas.clone()),
+            limite_exposicion: Some(self.limite_exposicion),
+            escalar_por_reputacion: Some(self.escalar_por_reputacion),
+            reserva: Some(self.reserva),
+            prestamos: Some(self.prestamos),
+            tasa_prestamo: Some(self.tasa_prestamo),
+            plazo_prestamo: Some(self.plazo_prestamo),
+            pool_staking: self.pool_staking.clone(),
+        }
+    }
+
+    pub fn es_caja_ahorro(&self) -> bool {
+        self.ahorro.is_some()
+    }
+
+    // En las cajas de ahorro el número de periodos no depende de los integrantes.
+    pub fn num_periodos(&self) -> u32 {
+        match &self.ahorro {
+            Some(caja) => caja.num_periodos,
+            None => self.num_integrantes,
+        }
+    }
+
+    pub fn actualizar_fecha_final(&mut self) {
+        self.fecha_final = date_handling::agregar_dias(
+            &self.fecha_inicio,
+            (self.num_periodos() * self.periodo - 1) as i64,
+        );
+    }
+
+    // El creador siempre es administrador de la Tanda.
+    pub fn rol(&self, cuenta: &AccountId) -> Option<RolOrganizador> {
+        if cuenta == &self.creador {
+            return Some(RolOrganizador::Administrador);
+        }
+
+        self.organizadores.get(cuenta).cloned()
+    }
+
+    pub fn es_administrador(&self, cuenta: &AccountId) -> bool {
+        self.rol(cuenta) == Some(RolOrganizador::Administrador)
+    }
+
+    // El árbitro no puede ser parte de la Tanda que arbitra.
+    pub fn validar_arbitro(&self, arbitro: &AccountId) {
+        assert!(
+            arbitro != &self.creador
+                && !self.organizadores.contains_key(arbitro)
+                && !self.integrantes.contains(arbitro),
+            "El usuario {} es organizador o integrante de esta Tanda, no puede ser su árbitro.",
+            arbitro
+        );
+    }
+
+    pub fn num_administradores(&self) -> u32 {
+        let administradores = self
+            .organizadores
+            .iter()
+            .filter(|(cuenta, rol)| {
+                **rol == RolOrganizador::Administrador && **cuenta != self.creador
+            })
+            .count();
+
+        administradores as u32 + 1
+    }
+
+    pub fn agregar_integrante(&mut self, integrante: AccountId) {
+        self.agregar_participacion(integrante, None, PARTICIPACION_COMPLETA);
+    }
+
+    pub fn agregar_participacion(
+        &mut self,
+        integrante: AccountId,
+        num_asiento: Option<u32>,
+        participacion: u32,
+    ) {
+        assert!(
+            !self.activa && self.estado == "Pendiente",
+            "Sólo se pueden agregar integrantes a Tandas pendientes."
+        );
+        assert!(
+            self.arbitro.as_ref() != Some(&integrante),
+            "El usuario {} es el árbitro de esta Tanda.",
+            &integrante
+        );
+        assert!(
+            participacion > 0 && participacion <= PARTICIPACION_COMPLETA,
+            "La participación debe estar entre 1 y {}.",
+            PARTICIPACION_COMPLETA
+        );
+
+        match num_asiento {
+            Some(num_asiento) => {
+                assert!(
+                    num_asiento > 0 && num_asiento as usize <= self.asientos.len(),
+                    "El asiento {} no existe.",
+                    num_asiento
+                );
+
+                let asiento = &mut self.asientos[num_asiento as usize - 1];
+                assert!(
+                    participacion <= asiento.disponible(),
+                    "El asiento {} sólo tiene {}% disponible.",
+                    num_asiento,
+                    asiento.disponible()
+                );
+
+                *asiento
+                    .participaciones
+                    .entry(String::from(&integrante))
+                    .or_insert(0) += participacion;
+            }
+            None => {
+                if u32::try_from(self.asientos.len()).unwrap() >= self.num_integrantes {
+                    env::log(
+                        "La Tanda se encuentra llena, ya no existen lugares disponibles."
+                            .as_bytes(),
+                    );
+                    return;
+                }
+
+                let mut asiento = Asiento::new(self.asientos.len() as u32 + 1);
+                asiento
+                    .participaciones
+                    .insert(String::from(&integrante), participacion);
+                self.asientos.push(asiento);
+            }
+        }
+
+        self.integrantes.insert(String::from(&integrante));
+        env::log(
+            format!(
+                "Integrante {} agregado exitosamente con {}% de un asiento.",
+                integrante, participacion
+            )
+            .as_bytes(),
+        );
+    }
+
+    pub fn asientos_completos(&self) -> bool {
+        u32::try_from(self.asientos.len()).unwrap() == self.num_integrantes
+            && self
+                .asientos
+                .iter()
+                .all(|asiento| asiento.disponible() == 0)
+    }
+
+    pub fn participacion(&self, cuenta: &AccountId) -> u32 {
+        self.asientos
+            .iter()
+            .filter_map(|asiento| asiento.participaciones.get(cuenta))
+            .sum()
+    }
+
+    pub fn cuota(&self, cuenta: &AccountId) -> u128 {
+        self.cuota_monto(cuenta, self.monto)
+    }
+
+    pub fn cuota_monto(&self, cuenta: &AccountId, monto: u32) -> u128 {
+        crate::prorrata(
+            crate::one_near() * monto as u128,
+            self.participacion(cuenta) as u128,
+            PARTICIPACION_COMPLETA as u128,
+        )
+    }
+
+    // Recargo por pagar después de que terminó el periodo, en porcentaje de la cuota.
+    pub fn penalizacion_monto(&self, cuenta: &AccountId, periodo: &Periodo) -> u128 {
+        if self.penalizacion == 0 || !date_handling::periodo_vencido(&periodo.fin) {
+            return 0;
+        }
+
+        self.cuota_monto(cuenta, periodo.monto) * self.penalizacion as u128 / 100
+    }
+
+    // Aportación al fondo de reserva que se cobra junto con la cuota.
+    pub fn reserva_monto(&self, cuenta: &AccountId, periodo: &Periodo) -> u128 {
+        if self.reserva == 0 || self.es_caja_ahorro() {
+            return 0;
+        }
+
+        self.cuota_monto(cuenta, periodo.monto) * self.reserva as u128 / 100
+    }
+
+    // Lo que falta por pagar del periodo, incluyendo la penalización por atraso y la reserva.
+    pub fn adeudo_total(&self, cuenta: &AccountId, periodo: &Periodo) -> u128 {
+        let adeudo = periodo.adeudo(cuenta, self.cuota_monto(cuenta, periodo.monto));
+        if adeudo == 0 {
+            return 0;
+        }
+
+        adeudo + self.penalizacion_monto(cuenta, periodo) + self.reserva_monto(cuenta, periodo)
+    }
+
+    // Lo que se espera recaudar en el contrato, sin las cuotas pagadas fuera de la cadena.
+    pub fn cuota_periodo(&self, periodo: &Periodo) -> u128 {
+        self.integrantes
+            .iter()
+            .filter(|integrante| !periodo.pagos_externos.contains(*integrante))
+            .map(|integrante| self.cuota_monto(integrante, periodo.monto))
+            .sum()
+    }
+
+    // Lo que recibe la cuenta cuando se pague el periodo, según su parte del asiento en turno.
+    pub fn pago_turno(&self, cuenta: &AccountId, periodo: &Periodo) -> u128 {
+        let participacion = match periodo.asiento_en_turno {
+            Some(numero) => self.asientos[numero as usize - 1]
+                .participaciones
+                .get(cuenta)
+                .copied()
+                .unwrap_or(0),
+            None if &periodo.usuario_en_turno == cuenta => PARTICIPACION_COMPLETA,
+            None => 0,
+        };
+
+        crate::prorrata(
+            self.cuota_periodo(periodo),
+            participacion as u128,
+            PARTICIPACION_COMPLETA as u128,
+        )
+    }
+
+    // Quita al integrante de sus asientos y devuelve los asientos que quedaron vacíos.
+    pub fn quitar_integrante(&mut self, integrante: &AccountId) -> Vec<u32> {
+        let mut vacios = Vec::new();
+
+        for asiento in self.asientos.iter_mut() {
+            if asiento.participaciones.remove(integrante).is_some()
+                && asiento.participaciones.is_empty()
+            {
+                vacios.push(asiento.numero);
+            }
+        }
+
+        self.integrantes.remove(integrante);
+        self.renovaciones.remove(integrante);
+
+        vacios
+    }
+
+    // Cede la participación del integrante en el asiento y devuelve el porcentaje cedido.
+    pub fn traspasar_asiento(
+        &mut self,
+        numero: u32,
+        cedente: &AccountId,
+        cesionario: &AccountId,
+    ) -> u32 {
+        let asiento = &mut self.asientos[numero as usize - 1];
+        let participacion = asiento.participaciones.remove(cedente).unwrap_or(0);
+
+        *asiento
+            .participaciones
+            .entry(String::from(cesionario))
+            .or_insert(0) += participacion;
+
+        if self.participacion(cedente) == 0 {
+            self.integrantes.remove(cedente);
+            self.renovaciones.remove(cedente);
+        }
+        self.integrantes.insert(String::from(cesionario));
+
+        participacion
+    }
+
+    pub fn eliminar_asiento(&mut self, numero: u32) {
+        self.liberar_asiento(numero);
+        self.num_integrantes -= 1;
+    }
+
+    // Quita el asiento sin reducir el número de integrantes, el lugar queda disponible.
+    pub fn liberar_asiento(&mut self, numero: u32) {
+        self.asientos.remove(numero as usize - 1);
+
+        for asiento in self.asientos.iter_mut() {
+            if asiento.numero > numero {
+                asiento.numero -= 1;
+            }
         }
     }
 }
 
-impl Default for Tanda {
-    fn default() -> Self {
-        Tanda {
-            id: String::from(""),
-            creador: String::from(""),
-            nombre_tanda: String::from(""),
-            num_integrantes: 0,
-            monto: 0,
-            fecha_inicio: String::from(""),
-            fecha_final: String::from(""),
-            activa: false,
-            periodo: 0,
-            estado: String::from(""),
-            integrantes: HashSet::new(),
+// * ASIENTO
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Asiento {
+    pub numero: u32,
+    pub participaciones: HashMap<AccountId, u32>,
+}
+
+impl Asiento {
+    pub fn new(numero: u32) -> Self {
+        Self {
+            numero,
+            participaciones: HashMap::new(),
         }
     }
+
+    pub fn disponible(&self) -> u32 {
+        PARTICIPACION_COMPLETA - self.participaciones.values().sum::<u32>()
+    }
+}
+
+// * OPCIONES DE TANDA
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct OpcionesTanda {
+    pub pago_automatico: Option<bool>,
+    pub pagos_parciales: Option<bool>,
+    pub asignacion_turnos: Option<AsignacionTurnos>,
+    pub renovable: Option<bool>,
+    pub orden_renovacion: Option<OrdenTurnos>,
+    pub quorum: Option<u32>,
+    pub umbral: Option<u32>,
+    pub aprobaciones: Option<u32>,
+    pub arbitro: Option<AccountId>,
+    pub confirmar_receptor: Option<bool>,
+    pub penalizacion: Option<u32>,
+    // Impide unirse a cuentas con incumplimientos sin resolver en otras Tandas.
+    pub bloquear_morosos: Option<bool>,
+    // Tipos de atestación vigente que se piden para unirse, por ejemplo "kyc-basic".
+    pub atestaciones_requeridas: Option<Vec<String>>,
+    // Máximo en NEAR que un integrante puede tener comprometido cada 30 días entre todas sus Tandas,
+    // las cuotas de periodos más cortos o más largos se llevan a ese lapso.
+    pub limite_exposicion: Option<u32>,
+    pub escalar_por_reputacion: Option<bool>,
+    // Porcentaje de cada cuota que se aparta para el fondo de reserva.
+    pub reserva: Option<u32>,
+    pub prestamos: Option<bool>,
+    // Interés total del préstamo en porcentaje y plazo para pagarlo en días.
+    pub tasa_prestamo: Option<u32>,
+    pub plazo_prestamo: Option<u32>,
+    // Una cuenta vacía desactiva el staking.
+    pub pool_staking: Option<AccountId>,
+}
+
+// * ORDEN DE TURNOS
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub enum OrdenTurnos {
+    #[default]
+    Rotar,
+    Sortear,
+}
+
+// * ASIGNACIÓN DE TURNOS
+// ? Con Sorteo el receptor de cada periodo se sortea cuando el periodo se completa.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AsignacionTurnos {
+    #[default]
+    Eleccion,
+    Sorteo,
 }
 
 // * PERIODO
@@ -86,47 +648,72 @@ pub struct Periodo {
     pub inicio: String,
     pub fin: String,
     pub usuario_en_turno: AccountId,
+    pub asiento_en_turno: Option<u32>,
+    pub monto: u32,
     pub pagos_completos: bool,
     pub tanda_pagada: bool,
-    pub cantidad_recaudada: u32,
+    pub cantidad_recaudada: U128,
     pub integrantes_pagados: HashSet<AccountId>,
+    pub abonos_parciales: HashMap<AccountId, U128>,
+    pub aportaciones: HashMap<AccountId, U128>,
+    pub beneficiarios: HashMap<AccountId, U128>,
+    pub pagos_externos: HashSet<AccountId>,
+    pub externos_por_confirmar: HashMap<AccountId, HashSet<AccountId>>,
 }
 
 impl Periodo {
-    pub fn new(inicio: String, fin: String) -> Self {
+    pub fn new(inicio: String, fin: String, monto: u32) -> Self {
         Self {
             inicio,
             fin,
             usuario_en_turno: String::from(""),
+            asiento_en_turno: None,
+            monto,
             pagos_completos: false,
             tanda_pagada: false,
-            cantidad_recaudada: 0,
+            cantidad_recaudada: U128(0),
             integrantes_pagados: HashSet::new(),
+            abonos_parciales: HashMap::new(),
+            aportaciones: HashMap::new(),
+            beneficiarios: HashMap::new(),
+            pagos_externos: HashSet::new(),
+            externos_por_confirmar: HashMap::new(),
         }
     }
-}
 
-impl Default for Periodo {
-    fn default() -> Self {
-        Periodo {
-            inicio: String::from(""),
-            fin: String::from(""),
-            usuario_en_turno: String::from(""),
-            pagos_completos: false,
-            tanda_pagada: false,
-            cantidad_recaudada: 0,
-            integrantes_pagados: HashSet::new(),
+    // Lo aportado por la cuenta en este periodo, incluyendo abonos parciales.
+    pub fn aportado(&self, cuenta: &AccountId) -> u128 {
+        self.aportaciones
+            .get(cuenta)
+            .map(|monto| monto.0)
+            .unwrap_or(0)
+            + self
+                .abonos_parciales
+                .get(cuenta)
+                .map(|monto| monto.0)
+                .unwrap_or(0)
+    }
+
+    pub fn adeudo(&self, cuenta: &AccountId, cuota: u128) -> u128 {
+        if self.integrantes_pagados.contains(cuenta) {
+            return 0;
+        }
+
+        match self.abonos_parciales.get(cuenta) {
+            Some(abonado) => cuota - abonado.0,
+            None => cuota,
         }
     }
 }
 
 // * USUARIO
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Default)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Usuario {
     pub cuenta: AccountId,
     pub tandas_creadas: Vec<String>,
     pub tandas_inscritas: Vec<String>,
+    pub perfil: Option<Perfil>,
 }
 
 impl Usuario {
@@ -135,16 +722,45 @@ impl Usuario {
             cuenta,
             tandas_creadas: Vec::<String>::new(),
             tandas_inscritas: Vec::<String>::new(),
+            perfil: None,
         }
     }
 }
 
-impl Default for Usuario {
-    fn default() -> Self {
-        Usuario {
-            cuenta: String::from(""),
-            tandas_creadas: Vec::<String>::new(),
-            tandas_inscritas: Vec::<String>::new(),
+// * PERFIL
+pub const LONGITUD_MAXIMA_PERFIL: usize = 256;
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Perfil {
+    pub nombre: Option<String>,
+    // URL o CID de IPFS.
+    pub avatar: Option<String>,
+    pub idioma: Option<String>,
+    pub contacto: Option<String>,
+    pub zona_horaria: Option<String>,
+}
+
+impl Perfil {
+    // Sólo cambian los campos indicados, una cadena vacía borra el campo.
+    pub fn aplicar(&mut self, cambios: Perfil) {
+        let campos = [
+            (&mut self.nombre, cambios.nombre),
+            (&mut self.avatar, cambios.avatar),
+            (&mut self.idioma, cambios.idioma),
+            (&mut self.contacto, cambios.contacto),
+            (&mut self.zona_horaria, cambios.zona_horaria),
+        ];
+
+        for (campo, cambio) in campos {
+            if let Some(valor) = cambio {
+                assert!(
+                    valor.len() <= LONGITUD_MAXIMA_PERFIL,
+                    "Los campos del perfil no pueden exceder {} caracteres.",
+                    LONGITUD_MAXIMA_PERFIL
+                );
+                *campo = if valor.is_empty() { None } else { Some(valor) };
+            }
         }
     }
 }
@@ -153,21 +769,735 @@ impl Default for Usuario {
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct Pago {
-    monto: u32,
-    fecha_pago: String,
+    pub id: String,
+    pub pagador: AccountId,
+    pub indice_periodo: u32,
+    pub monto: U128,
+    pub token: String,
+    pub bloque: u64,
+    pub fecha_pago: String,
+    pub timestamp: U64,
+    pub a_tiempo: bool,
+    pub penalizacion: U128,
+    pub reserva: U128,
+    pub fuera_de_cadena: bool,
 }
 
 impl Pago {
-    pub fn new(monto: u32, fecha_pago: String) -> Self {
-        Self { monto, fecha_pago }
+    pub fn new(pagador: AccountId, indice_periodo: u32, monto: u128, periodo: &Periodo) -> Self {
+        Self {
+            id: String::new(),
+            pagador,
+            indice_periodo,
+            monto: U128(monto),
+            token: String::from(TOKEN_NEAR),
+            bloque: env::block_index(),
+            fecha_pago: date_handling::calcular_inicio(),
+            timestamp: U64(env::block_timestamp()),
+            a_tiempo: !date_handling::periodo_vencido(&periodo.fin),
+            penalizacion: U128(0),
+            reserva: U128(0),
+            fuera_de_cadena: false,
+        }
+    }
+
+    // Pago en efectivo, los fondos nunca pasaron por el contrato.
+    pub fn fuera_de_cadena(
+        pagador: AccountId,
+        indice_periodo: u32,
+        monto: u128,
+        periodo: &Periodo,
+    ) -> Self {
+        Self {
+            fuera_de_cadena: true,
+            ..Self::new(pagador, indice_periodo, monto, periodo)
+        }
+    }
+}
+
+// * MOVIMIENTO DE SALDO
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ConceptoMovimiento {
+    PagoTanda,
+    Reembolso,
+    ReembolsoMonedero,
+    Retiro,
+    RetiroFallido,
+    VentaAsiento,
+    Rendimiento,
+    RetiroAhorro,
+    Prestamo,
+    Intereses,
+    Reserva,
+    CobroDeuda,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MovimientoSaldo {
+    pub cuenta: AccountId,
+    pub concepto: ConceptoMovimiento,
+    pub abono: bool,
+    pub monto: U128,
+    pub saldo_resultante: U128,
+    pub referencia: String,
+    pub bloque: u64,
+    pub fecha: String,
+}
+
+impl MovimientoSaldo {
+    pub fn new(
+        cuenta: AccountId,
+        concepto: ConceptoMovimiento,
+        abono: bool,
+        monto: u128,
+        saldo_resultante: u128,
+        referencia: String,
+    ) -> Self {
+        Self {
+            cuenta,
+            concepto,
+            abono,
+            monto: U128(monto),
+            saldo_resultante: U128(saldo_resultante),
+            referencia,
+            bloque: env::block_index(),
+            fecha: date_handling::calcular_inicio(),
+        }
+    }
+}
+
+// * MONEDERO
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EstadoMonedero {
+    pub saldo: U128,
+    pub periodos_prepagados: u32,
+}
+
+// * ORGANIZADORES
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum RolOrganizador {
+    // Ejecuta y aprueba las acciones administrativas.
+    Administrador,
+    // Sólo puede solicitar acciones administrativas.
+    Auxiliar,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AccionAdministrativa {
+    Activar,
+    RemoverIntegrante {
+        cuenta: AccountId,
+    },
+    Cancelar,
+    RegenerarPeriodos,
+    Editar {
+        nombre: Option<String>,
+        num_integrantes: Option<u32>,
+        monto: Option<u32>,
+        periodo: Option<u32>,
+        fecha_inicio: Option<String>,
+    },
+    Configurar {
+        opciones: OpcionesTanda,
+    },
+    AgregarOrganizador {
+        cuenta: AccountId,
+        rol: RolOrganizador,
+    },
+    QuitarOrganizador {
+        cuenta: AccountId,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SolicitudAdministrativa {
+    pub id: u32,
+    pub accion: AccionAdministrativa,
+    pub solicitante: AccountId,
+    pub aprobaciones: HashSet<AccountId>,
+    pub estado: String,
+    pub fecha: String,
+}
+
+impl SolicitudAdministrativa {
+    pub fn new(id: u32, accion: AccionAdministrativa) -> Self {
+        Self {
+            id,
+            accion,
+            solicitante: env::predecessor_account_id(),
+            aprobaciones: HashSet::new(),
+            estado: String::from("Pendiente"),
+            fecha: date_handling::calcular_inicio(),
+        }
+    }
+}
+
+// * ESTADO DE CUENTA
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TurnoIntegrante {
+    pub indice_periodo: u32,
+    pub num_asiento: Option<u32>,
+    pub fecha: String,
+    pub pagado: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EstadoCuentaTanda {
+    pub tanda: String,
+    pub nombre_tanda: String,
+    pub estado: String,
+    pub proximo_periodo: Option<u32>,
+    pub fecha_limite: Option<String>,
+    pub monto_a_pagar: U128,
+    pub periodos_vencidos: u32,
+    pub adeudo_vencido: U128,
+    pub turnos: Vec<TurnoIntegrante>,
+    pub total_pagado: U128,
+    pub total_recibido: U128,
+}
+
+// * CAJA DE AHORRO
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ConfiguracionAhorro {
+    pub num_periodos: u32,
+    pub meta: u32,
+    pub penalizacion_retiro: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CajaAhorro {
+    pub num_periodos: u32,
+    pub meta: u32,
+    pub penalizacion_retiro: u32,
+    pub fondo_retiros: U128,
+    pub retirados: HashSet<AccountId>,
+}
+
+impl CajaAhorro {
+    pub fn new(configuracion: ConfiguracionAhorro) -> Self {
+        Self {
+            num_periodos: configuracion.num_periodos,
+            meta: configuracion.meta,
+            penalizacion_retiro: configuracion.penalizacion_retiro,
+            fondo_retiros: U128(0),
+            retirados: HashSet::new(),
+        }
+    }
+
+    // Misma configuración para un nuevo ciclo, sin penalizaciones ni retiros.
+    pub fn reiniciar(&self) -> Self {
+        Self {
+            num_periodos: self.num_periodos,
+            meta: self.meta,
+            penalizacion_retiro: self.penalizacion_retiro,
+            fondo_retiros: U128(0),
+            retirados: HashSet::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ResumenAhorro {
+    pub meta: U128,
+    pub ahorrado: U128,
+    pub fecha_vencimiento: String,
+    pub fondo_retiros: U128,
+    pub ahorradores: HashMap<AccountId, U128>,
+    pub retirados: HashSet<AccountId>,
+    pub meta_alcanzada: bool,
+    pub liquidable: bool,
+}
+
+// * EXPOSICIÓN
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Exposicion {
+    pub cuenta: AccountId,
+    pub compromisos: HashMap<String, U128>,
+    pub total: U128,
+    pub limite_global: U128,
+    pub reputacion: u32,
+}
+
+// * STAKING
+// Las acciones representan la parte de la Tanda en lo que el contrato tiene delegado al pool.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EstadoStaking {
+    pub acciones: U128,
+    pub depositado: U128,
+    pub principal_por_retirar: U128,
+    pub rendimiento_por_retirar: U128,
+    pub rendimiento: U128,
+    pub rendimiento_repartido: bool,
+}
+
+impl EstadoStaking {
+    pub fn new() -> Self {
+        Self {
+            acciones: U128(0),
+            depositado: U128(0),
+            principal_por_retirar: U128(0),
+            rendimiento_por_retirar: U128(0),
+            rendimiento: U128(0),
+            rendimiento_repartido: false,
+        }
+    }
+
+    // Fondos de la Tanda que no están disponibles en el contrato.
+    pub fn inmovilizado(&self) -> u128 {
+        self.depositado.0 + self.principal_por_retirar.0
+    }
+}
+
+impl Default for EstadoStaking {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Lo que se registró al desdelegar, para revertirlo si el pool rechaza la operación.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Desdelegacion {
+    pub acciones: U128,
+    pub depositado: U128,
+    pub principal: U128,
+    pub rendimiento: U128,
+}
+
+// Lo que el contrato tiene en un pool se comparte entre todas las Tandas que lo usan.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EstadoPool {
+    pub acciones: U128,
+    pub epoca_retiro: U64,
+    pub operacion_pendiente: bool,
+    pub habilitado: bool,
+}
+
+impl EstadoPool {
+    pub fn new() -> Self {
+        Self {
+            acciones: U128(0),
+            epoca_retiro: U64(0),
+            operacion_pendiente: false,
+            habilitado: false,
+        }
+    }
+}
+
+impl Default for EstadoPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// * PROPUESTA
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TipoPropuesta {
+    CambiarMonto { monto: u32 },
+    CambiarPeriodo { periodo: u32 },
+    Expulsar { cuenta: AccountId },
+    Pausar,
+    Reanudar,
+    Disolver,
+    ReemplazarCreador { cuenta: AccountId },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Propuesta {
+    pub id: u32,
+    pub tipo: TipoPropuesta,
+    pub proponente: AccountId,
+    pub votos_favor: HashSet<AccountId>,
+    pub votos_contra: HashSet<AccountId>,
+    pub quorum: u32,
+    pub umbral: u32,
+    pub estado: String,
+    pub fecha: String,
+    pub cierre: String,
+}
+
+impl Propuesta {
+    pub fn new(id: u32, tipo: TipoPropuesta, quorum: u32, umbral: u32) -> Self {
+        let fecha = date_handling::calcular_inicio();
+
+        Self {
+            id,
+            tipo,
+            proponente: env::predecessor_account_id(),
+            votos_favor: HashSet::new(),
+            votos_contra: HashSet::new(),
+            quorum,
+            umbral,
+            estado: String::from("Abierta"),
+            cierre: date_handling::agregar_dias(&fecha, DIAS_VOTACION),
+            fecha,
+        }
+    }
+
+    pub fn vencida(&self) -> bool {
+        date_handling::fecha_alcanzada(&self.cierre)
+    }
+
+    // Antes del cierre, aún si los votos pendientes fueran en contra, la propuesta alcanzaría el umbral.
+    pub fn aprobada(&self, votantes: u32) -> bool {
+        let favor = self.votos_favor.len() as u32;
+        let emitidos = favor + self.votos_contra.len() as u32;
+        let base = if self.vencida() {
+            emitidos
+        } else {
+            cmp::max(votantes, emitidos)
+        };
+
+        emitidos * 100 >= self.quorum * votantes && favor * 100 > self.umbral * base
+    }
+
+    // Aún si los votos pendientes fueran a favor, la propuesta no alcanzaría el umbral.
+    pub fn rechazada(&self, votantes: u32) -> bool {
+        if self.vencida() {
+            return !self.aprobada(votantes);
+        }
+
+        let favor = self.votos_favor.len() as u32;
+        let emitidos = favor + self.votos_contra.len() as u32;
+        let pendientes = votantes.saturating_sub(emitidos);
+
+        (favor + pendientes) * 100 <= self.umbral * (emitidos + pendientes)
+    }
+}
+
+// * LIQUIDACION
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Liquidacion {
+    pub cuenta: AccountId,
+    pub aportado: U128,
+    pub recibido: U128,
+    pub reembolso: U128,
+    pub pendiente_por_cobrar: U128,
+    pub deuda: U128,
+}
+
+// * DEUDA
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Deuda {
+    pub deudor: AccountId,
+    pub tanda: String,
+    pub monto: U128,
+    pub acreedores: HashMap<AccountId, U128>,
+    pub fecha: String,
+    pub saldada: bool,
+}
+
+impl Deuda {
+    pub fn new(deudor: AccountId, tanda: String, monto: u128) -> Self {
+        Self {
+            deudor,
+            tanda,
+            monto: U128(monto),
+            acreedores: HashMap::new(),
+            fecha: date_handling::calcular_inicio(),
+            saldada: false,
+        }
+    }
+}
+
+// * TRASPASO
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Traspaso {
+    pub id: u32,
+    pub asiento: u32,
+    pub cedente: AccountId,
+    pub cesionario: AccountId,
+    pub precio: U128,
+    pub estado: String,
+    pub fecha_oferta: String,
+    pub fecha_cierre: Option<String>,
+}
+
+impl Traspaso {
+    pub fn new(id: u32, asiento: u32, cesionario: AccountId, precio: u128) -> Self {
+        Self {
+            id,
+            asiento,
+            cedente: env::predecessor_account_id(),
+            cesionario,
+            precio: U128(precio),
+            estado: String::from("Pendiente"),
+            fecha_oferta: date_handling::calcular_inicio(),
+            fecha_cierre: None,
+        }
+    }
+}
+
+// * DISPUTA
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub enum MedidaArbitral {
+    MarcarPagado {
+        cuenta: AccountId,
+    },
+    DesmarcarPagado {
+        cuenta: AccountId,
+    },
+    ReasignarTurno {
+        cuenta: AccountId,
+        num_asiento: Option<u32>,
+    },
+    Reembolsar {
+        cuenta: AccountId,
+        monto: U128,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Resolucion {
+    pub arbitro: AccountId,
+    pub dictamen: String,
+    pub medidas: Vec<MedidaArbitral>,
+    pub fecha: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Disputa {
+    pub id: u32,
+    pub indice_periodo: u32,
+    pub promovente: AccountId,
+    pub motivo: String,
+    pub estado: String,
+    pub fecha: String,
+    pub vencimiento: String,
+    pub resolucion: Option<Resolucion>,
+}
+
+impl Disputa {
+    pub fn new(id: u32, indice_periodo: u32, motivo: String) -> Self {
+        let fecha = date_handling::calcular_inicio();
+
+        Self {
+            id,
+            indice_periodo,
+            promovente: env::predecessor_account_id(),
+            motivo,
+            estado: String::from("Abierta"),
+            vencimiento: date_handling::agregar_dias(&fecha, DIAS_DISPUTA),
+            fecha,
+            resolucion: None,
+        }
+    }
+
+    // Abierta y sin vencer, sólo así congela los pagos de la Tanda.
+    pub fn vigente(&self) -> bool {
+        self.estado == "Abierta" && !date_handling::fecha_alcanzada(&self.vencimiento)
+    }
+}
+
+// * PRÉSTAMOS
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Prestamo {
+    pub id: u32,
+    pub deudor: AccountId,
+    pub monto: U128,
+    pub interes: U128,
+    pub pagado: U128,
+    pub estado: String,
+    pub fecha: String,
+    pub vencimiento: String,
+}
+
+impl Prestamo {
+    pub fn new(id: u32, deudor: AccountId, monto: u128, interes: u128, plazo: u32) -> Self {
+        Self {
+            id,
+            deudor,
+            monto: U128(monto),
+            interes: U128(interes),
+            pagado: U128(0),
+            estado: String::from("Vigente"),
+            fecha: date_handling::calcular_inicio(),
+            vencimiento: date_handling::agregar_dias(
+                &date_handling::calcular_inicio(),
+                plazo as i64,
+            ),
+        }
+    }
+
+    pub fn pendiente(&self) -> u128 {
+        self.monto.0 + self.interes.0 - self.pagado.0
+    }
+
+    // Los pagos cubren primero los intereses y después el capital.
+    pub fn capital_pendiente(&self) -> u128 {
+        self.monto.0 - self.pagado.0.saturating_sub(self.interes.0)
+    }
+
+    // Aplica el abono y devuelve la parte que correspondió a intereses.
+    pub fn abonar(&mut self, abono: u128) -> u128 {
+        let intereses_antes = cmp::min(self.pagado.0, self.interes.0);
+        self.pagado = U128(self.pagado.0 + abono);
+        let intereses_despues = cmp::min(self.pagado.0, self.interes.0);
+
+        if self.pendiente() == 0 {
+            self.estado = String::from("Liquidado");
+        }
+
+        intereses_despues - intereses_antes
     }
+
+    pub fn vencido(&self) -> bool {
+        self.estado == "Vigente" && date_handling::fecha_alcanzada(&self.vencimiento)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct CarteraPrestamos {
+    pub prestamos: Vec<Prestamo>,
+    pub intereses_por_repartir: U128,
 }
 
-impl Default for Pago {
+impl Default for CarteraPrestamos {
     fn default() -> Self {
-        Pago {
-            monto: 0,
-            fecha_pago: String::from(""),
+        Self {
+            prestamos: Vec::new(),
+            intereses_por_repartir: U128(0),
         }
     }
 }
+
+impl CarteraPrestamos {
+    pub fn vigente(&self, cuenta: &AccountId) -> Option<usize> {
+        self.prestamos
+            .iter()
+            .position(|prestamo| &prestamo.deudor == cuenta && prestamo.estado == "Vigente")
+    }
+
+    // Capital prestado que no ha regresado al fondo, sin contar a las cuentas indicadas.
+    pub fn prestado(&self, excepto: &[AccountId]) -> u128 {
+        self.prestamos
+            .iter()
+            .filter(|prestamo| prestamo.estado == "Vigente" && !excepto.contains(&prestamo.deudor))
+            .map(|prestamo| prestamo.capital_pendiente())
+            .sum()
+    }
+}
+
+// * FONDO DE RESERVA
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Adelanto {
+    pub deudor: AccountId,
+    pub indice_periodo: u32,
+    pub monto: U128,
+    pub pagado: U128,
+    pub fecha: String,
+}
+
+impl Adelanto {
+    pub fn new(deudor: AccountId, indice_periodo: u32, monto: u128) -> Self {
+        Self {
+            deudor,
+            indice_periodo,
+            monto: U128(monto),
+            pagado: U128(0),
+            fecha: date_handling::calcular_inicio(),
+        }
+    }
+
+    pub fn pendiente(&self) -> u128 {
+        self.monto.0 - self.pagado.0
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FondoReserva {
+    pub saldo: U128,
+    pub adelantos: Vec<Adelanto>,
+    // Lo que cada integrante aportó con sus cuotas, define su parte al repartir la reserva.
+    pub aportaciones: HashMap<AccountId, U128>,
+}
+
+impl Default for FondoReserva {
+    fn default() -> Self {
+        Self {
+            saldo: U128(0),
+            adelantos: Vec::new(),
+            aportaciones: HashMap::new(),
+        }
+    }
+}
+
+impl FondoReserva {
+    pub fn aportar(&mut self, cuenta: &AccountId, monto: u128) {
+        let aportado = self
+            .aportaciones
+            .get(cuenta)
+            .map_or(0, |aportado| aportado.0);
+        self.aportaciones
+            .insert(String::from(cuenta), U128(aportado + monto));
+        self.saldo = U128(self.saldo.0 + monto);
+    }
+
+    // Lo que la cuenta aún debe a la reserva por los faltantes que se le cubrieron.
+    pub fn pendiente(&self, cuenta: &AccountId) -> u128 {
+        self.adelantos
+            .iter()
+            .filter(|adelanto| &adelanto.deudor == cuenta)
+            .map(|adelanto| adelanto.pendiente())
+            .sum()
+    }
+}
+
+// * ATESTACIONES
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Atestacion {
+    pub tipo: String,
+    pub verificador: AccountId,
+    pub fecha: String,
+    pub vencimiento: Option<String>,
+    pub revocada: bool,
+}
+
+impl Atestacion {
+    pub fn new(tipo: String, vigencia: Option<u32>) -> Self {
+        Self {
+            tipo,
+            verificador: env::predecessor_account_id(),
+            fecha: date_handling::calcular_inicio(),
+            vencimiento: vigencia.map(|dias| {
+                date_handling::agregar_dias(&date_handling::calcular_inicio(), dias as i64)
+            }),
+            revocada: false,
+        }
+    }
+
+    pub fn vigente(&self) -> bool {
+        !self.revocada
+            && self
+                .vencimiento
+                .as_ref()
+                .is_none_or(|vencimiento| !date_handling::fecha_alcanzada(vencimiento))
+    }
+}